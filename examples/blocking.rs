@@ -0,0 +1,28 @@
+//! Sends a checkin without an async runtime.
+//!
+//! Usage: `cargo run --example blocking --features blocking,surf -- <webhook id> [note]`
+
+use std::env;
+
+use tissue_rs::{CheckinBuilder, CheckinResponse, IncomingEndpoint, SurfRequester};
+
+fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let mut args = env::args().skip(1);
+    let id = args.next().ok_or("webhook id is required")?;
+
+    let mut builder = CheckinBuilder::default();
+    if let Some(note) = args.next() {
+        builder.note(&note)?;
+    }
+    let checkin = builder
+        .try_build()
+        .map_err(|errors| format!("{:?}", errors))?;
+
+    let endpoint = IncomingEndpoint::new(&id, SurfRequester::new());
+    match endpoint.send_checkin_blocking(&checkin)? {
+        CheckinResponse::Success(received) => println!("Checked in: #{}", received.id()),
+        otherwise => println!("Failed: {:?}", otherwise),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,19 @@
+//! Sends a checkin from WebAssembly with `FetchRequester`.
+//!
+//! Check that it builds: `cargo build --example wasm --features wasm --target wasm32-unknown-unknown`
+
+#[cfg(target_arch = "wasm32")]
+fn main() {
+    use tissue_rs::{CheckinBuilder, FetchRequester, IncomingEndpoint};
+
+    wasm_bindgen_futures::spawn_local(async {
+        let endpoint = IncomingEndpoint::new("webhook-id", FetchRequester::new());
+        let checkin = CheckinBuilder::default().build();
+        let _ = endpoint.send_checkin(&checkin).await;
+    });
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn main() {
+    eprintln!("This example only runs on wasm32");
+}
@@ -0,0 +1,801 @@
+//! Contains the client for authenticated Tissue API.
+
+use crate::{
+    checkin::Checkin,
+    error::TissueError,
+    query::percent_encode,
+    tissue::{parse_response, CheckinResponse, ReceivedCheckin},
+    TissueRequester,
+};
+use std::{collections::HashMap, fmt::Display};
+
+use chrono::prelude::*;
+use futures::stream::{self, Stream, StreamExt};
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::{from_value, Value};
+
+/// Represents a page of listed items.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Page<T> {
+    items: Vec<T>,
+    page: u32,
+    total: Option<u64>,
+    last_page: Option<u32>,
+}
+
+impl<T> Page<T> {
+    /// Items in this page.
+    pub fn items(&self) -> &[T] {
+        &self.items
+    }
+
+    /// Consumes the page and returns its items.
+    pub fn into_items(self) -> Vec<T> {
+        self.items
+    }
+
+    /// Page number, starting from 1.
+    pub fn page(&self) -> u32 {
+        self.page
+    }
+
+    /// Total count of items in all pages, if Tissue returned it.
+    pub fn total(&self) -> Option<u64> {
+        self.total
+    }
+
+    /// Next page number. Returns `None` if this is the last page or it is unknown.
+    pub fn next_page(&self) -> Option<u32> {
+        self.last_page
+            .filter(|last_page| self.page < *last_page)
+            .map(|_| self.page + 1)
+    }
+}
+
+/// Filter for listing checkins.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct CheckinFilter {
+    since: Option<String>,
+    until: Option<String>,
+    tag: Option<String>,
+}
+
+impl CheckinFilter {
+    /// Creates an empty filter.
+    pub fn new() -> CheckinFilter {
+        CheckinFilter::default()
+    }
+
+    /// Lists only checkins at or after `since`.
+    pub fn with_since<Tz: TimeZone>(self, since: DateTime<Tz>) -> CheckinFilter
+    where
+        <Tz as TimeZone>::Offset: Display,
+    {
+        CheckinFilter {
+            since: Some(since.to_rfc3339_opts(SecondsFormat::Secs, true)),
+            ..self
+        }
+    }
+
+    /// Lists only checkins at or before `until`.
+    pub fn with_until<Tz: TimeZone>(self, until: DateTime<Tz>) -> CheckinFilter
+    where
+        <Tz as TimeZone>::Offset: Display,
+    {
+        CheckinFilter {
+            until: Some(until.to_rfc3339_opts(SecondsFormat::Secs, true)),
+            ..self
+        }
+    }
+
+    /// Lists only checkins with `tag`.
+    pub fn with_tag(self, tag: &str) -> CheckinFilter {
+        CheckinFilter {
+            tag: Some(tag.into()),
+            ..self
+        }
+    }
+
+    /// Appends the filter as query parameters.
+    fn append_query(&self, query: &mut String) {
+        let params = [
+            ("since", &self.since),
+            ("until", &self.until),
+            ("tag", &self.tag),
+        ];
+        for (key, value) in params.iter() {
+            if let Some(value) = value {
+                query.push_str(&format!("&{}={}", key, percent_encode(value)));
+            }
+        }
+    }
+}
+
+/// Profile of a Tissue user.
+/// Fields missing in the response are left empty, and unknown fields are ignored.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize)]
+pub struct User {
+    id: usize,
+    name: String,
+    #[serde(default)]
+    display_name: String,
+    #[serde(default)]
+    bio: Option<String>,
+    #[serde(default)]
+    url: Option<String>,
+    #[serde(default)]
+    checkins_count: Option<u64>,
+}
+
+impl User {
+    /// ID of user.
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    /// Name used in URLs.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Name for display.
+    pub fn display_name(&self) -> &str {
+        &self.display_name
+    }
+
+    /// Biography.
+    pub fn bio(&self) -> Option<&String> {
+        self.bio.as_ref()
+    }
+
+    /// Website URL.
+    pub fn url(&self) -> Option<&String> {
+        self.url.as_ref()
+    }
+
+    /// Count of checkins.
+    pub fn checkins_count(&self) -> Option<u64> {
+        self.checkins_count
+    }
+}
+
+/// Checkin statistics of a Tissue user.
+/// Fields missing in the response are left empty, and unknown fields are ignored.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize)]
+pub struct Stats {
+    #[serde(default)]
+    total_checkins: Option<u64>,
+    #[serde(default)]
+    current_streak_days: Option<u64>,
+    #[serde(default)]
+    longest_streak_days: Option<u64>,
+    #[serde(default)]
+    average_interval_seconds: Option<u64>,
+    #[serde(default)]
+    last_checked_in_at: Option<DateTime<Local>>,
+}
+
+impl Stats {
+    /// Count of all checkins.
+    pub fn total_checkins(&self) -> Option<u64> {
+        self.total_checkins
+    }
+
+    /// Days of the current streak.
+    pub fn current_streak_days(&self) -> Option<u64> {
+        self.current_streak_days
+    }
+
+    /// Days of the longest streak.
+    pub fn longest_streak_days(&self) -> Option<u64> {
+        self.longest_streak_days
+    }
+
+    /// Average interval between checkins in seconds.
+    pub fn average_interval_seconds(&self) -> Option<u64> {
+        self.average_interval_seconds
+    }
+
+    /// Timestamp of the latest checkin.
+    pub fn last_checked_in_at(&self) -> Option<&DateTime<Local>> {
+        self.last_checked_in_at.as_ref()
+    }
+}
+
+/// Tag suggested for a search query.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize)]
+pub struct TagSuggestion {
+    name: String,
+    #[serde(default)]
+    count: u64,
+}
+
+impl TagSuggestion {
+    /// Name of tag.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Count of checkins with the tag.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+}
+
+/// Represents a client for Tissue API authenticated with OAuth2 access token.
+/// The routes of its methods are not checked against an API reference of Tissue
+/// and may differ on some instances; `get`, `post`, `put` and `delete` reach any route.
+pub struct TissueClient<T> {
+    domain: String,
+    headers: HashMap<String, String>,
+    requester: T,
+}
+
+impl<T: TissueRequester> TissueClient<T> {
+    /// Creates a new client with access token.
+    pub fn new(token: &str, requester: T) -> TissueClient<T> {
+        TissueClient::with_domain("shikorism.net", token, requester)
+    }
+
+    /// Creates a new client with domain and access token.
+    pub fn with_domain(domain: &str, token: &str, requester: T) -> TissueClient<T> {
+        let mut headers = HashMap::new();
+        headers.insert("Authorization".into(), format!("Bearer {}", token));
+        TissueClient {
+            domain: domain.into(),
+            headers,
+            requester,
+        }
+    }
+
+    /// Adds a header sent with every request.
+    pub fn with_header(mut self, key: &str, value: &str) -> TissueClient<T> {
+        self.headers.insert(key.into(), value.into());
+        self
+    }
+
+    /// Creates a checkin.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(domain = %self.domain, kind))
+    )]
+    pub async fn create_checkin(&self, checkin: &Checkin) -> Result<CheckinResponse, TissueError> {
+        let response = self.post("/api/checkins", checkin.to_api_value()).await?;
+        parse_response(response)
+    }
+
+    /// Lists checkins of the authenticated user.
+    pub async fn list_checkins(
+        &self,
+        page: u32,
+        per_page: u32,
+    ) -> Result<Page<ReceivedCheckin>, TissueError> {
+        self.list_checkins_filtered(page, per_page, &CheckinFilter::new())
+            .await
+    }
+
+    /// Lists checkins of the authenticated user matching the filter.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(domain = %self.domain))
+    )]
+    pub async fn list_checkins_filtered(
+        &self,
+        page: u32,
+        per_page: u32,
+        filter: &CheckinFilter,
+    ) -> Result<Page<ReceivedCheckin>, TissueError> {
+        let mut path = format!("/api/checkins?page={}&per_page={}", page, per_page);
+        filter.append_query(&mut path);
+        let response = self.get(&path).await?;
+        parse_page(response, page)
+    }
+
+    /// Counts checkins of the authenticated user, from the total of a listing with one item.
+    /// Returns `TissueError::MalformedResponse` if the listing is not paginated.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(domain = %self.domain))
+    )]
+    pub async fn checkin_count(&self) -> Result<u64, TissueError> {
+        let response = self.get("/api/checkins?page=1&per_page=1").await?;
+        check_status(&response)?;
+        match response["total"].as_u64() {
+            Some(total) => Ok(total),
+            None => Err(TissueError::MalformedResponse(response)),
+        }
+    }
+
+    /// Streams checkins of the authenticated user, fetching pages lazily from the first one.
+    /// It ends after the last page, an empty or short page, or an error.
+    pub fn checkins_stream(
+        &self,
+        per_page: u32,
+    ) -> impl Stream<Item = Result<ReceivedCheckin, TissueError>> + '_ {
+        stream::unfold(Some(1), move |page| async move {
+            let page = page?;
+            match self.list_checkins(page, per_page).await {
+                Ok(listed) => {
+                    let next_page =
+                        if listed.items.is_empty() || listed.items.len() < per_page as usize {
+                            None
+                        } else if listed.last_page.is_some() {
+                            listed.next_page()
+                        } else {
+                            Some(page + 1)
+                        };
+                    let items: Vec<_> = listed.into_items().into_iter().map(Ok).collect();
+                    Some((stream::iter(items), next_page))
+                }
+                Err(error) => Some((stream::iter(vec![Err(error)]), None)),
+            }
+        })
+        .flatten()
+    }
+
+    /// Fetches a checkin by ID.
+    /// Returns `TissueError::NotFound` if it does not exist.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(domain = %self.domain, id = id))
+    )]
+    pub async fn get_checkin(&self, id: usize) -> Result<ReceivedCheckin, TissueError> {
+        let response = self.get(&format!("/api/checkins/{}", id)).await?;
+        check_status(&response)?;
+        Ok(from_value(response)?)
+    }
+
+    /// Updates a checkin by ID.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(domain = %self.domain, id = id, kind))
+    )]
+    pub async fn update_checkin(
+        &self,
+        id: usize,
+        checkin: &Checkin,
+    ) -> Result<CheckinResponse, TissueError> {
+        let path = format!("/api/checkins/{}", id);
+        let response = self.put(&path, checkin.to_api_value()).await?;
+        parse_response(response)
+    }
+
+    /// Deletes a checkin by ID.
+    /// Returns `TissueError::NotFound` if it does not exist.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(domain = %self.domain, id = id))
+    )]
+    pub async fn delete_checkin(&self, id: usize) -> Result<(), TissueError> {
+        let response = self.delete(&format!("/api/checkins/{}", id)).await?;
+        check_status(&response)
+    }
+
+    /// Fetches the profile of the authenticated user.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(domain = %self.domain))
+    )]
+    pub async fn current_user(&self) -> Result<User, TissueError> {
+        let response = self.get("/api/user").await?;
+        check_status(&response)?;
+        Ok(from_value(response)?)
+    }
+
+    /// Fetches the checkin statistics of the authenticated user.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(domain = %self.domain))
+    )]
+    pub async fn stats(&self) -> Result<Stats, TissueError> {
+        let response = self.get("/api/user/stats").await?;
+        check_status(&response)?;
+        Ok(from_value(response)?)
+    }
+
+    /// Searches tags starting with `query` for suggestion.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(domain = %self.domain))
+    )]
+    pub async fn search_tags(&self, query: &str) -> Result<Vec<TagSuggestion>, TissueError> {
+        let path = format!("/api/tags?q={}", percent_encode(query));
+        let response = self.get(&path).await?;
+        Ok(parse_page(response, 1)?.into_items())
+    }
+
+    /// Does an authenticated GET request to `path` (e.g. `/api/checkins`).
+    pub async fn get(&self, path: &str) -> Result<Value, TissueError> {
+        let url = self.url(path);
+        self.requester
+            .get(url.clone(), self.headers.clone())
+            .await
+            .map_err(|source| TissueError::Transport { url, source })
+    }
+
+    /// Does an authenticated POST request to `path` (e.g. `/api/checkins`).
+    pub async fn post(&self, path: &str, body: Value) -> Result<Value, TissueError> {
+        let url = self.url(path);
+        self.requester
+            .post(url.clone(), self.headers.clone(), body)
+            .await
+            .map_err(|source| TissueError::Transport { url, source })
+    }
+
+    /// Does an authenticated PUT request to `path` (e.g. `/api/checkins/1`).
+    pub async fn put(&self, path: &str, body: Value) -> Result<Value, TissueError> {
+        let url = self.url(path);
+        self.requester
+            .put(url.clone(), self.headers.clone(), body)
+            .await
+            .map_err(|source| TissueError::Transport { url, source })
+    }
+
+    /// Does an authenticated DELETE request to `path` (e.g. `/api/checkins/1`).
+    pub async fn delete(&self, path: &str) -> Result<Value, TissueError> {
+        let url = self.url(path);
+        self.requester
+            .delete(url.clone(), self.headers.clone())
+            .await
+            .map_err(|source| TissueError::Transport { url, source })
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("https://{}{}", self.domain, path)
+    }
+}
+
+/// Returns `Err` if the response has an error status.
+fn check_status(value: &Value) -> Result<(), TissueError> {
+    match value["status"].as_u64() {
+        Some(status @ 401) | Some(status @ 403) => Err(TissueError::Unauthorized(status as u16)),
+        Some(404) => Err(TissueError::NotFound),
+        Some(status) if status >= 400 => Err(TissueError::UnexpectedStatus {
+            status,
+            body: value.clone(),
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// Parses a paginated response, which is either a bare array or a paginator object.
+/// A bare array may come in an object as `body` from the requester.
+fn parse_page<T: DeserializeOwned>(mut value: Value, page: u32) -> Result<Page<T>, TissueError> {
+    check_status(&value)?;
+    if value["body"].is_array() {
+        value = value["body"].take();
+    }
+    if value.is_array() {
+        return Ok(Page {
+            items: from_value(value)?,
+            page,
+            total: None,
+            last_page: None,
+        });
+    }
+
+    Ok(Page {
+        items: from_value(value["data"].take())?,
+        page,
+        total: value["total"].as_u64(),
+        last_page: value["last_page"]
+            .as_u64()
+            .map(|last_page| last_page as u32),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{checkin::CheckinBuilder, requester::MockRequester};
+
+    use futures::executor::block_on;
+    use serde_json::json;
+
+    #[test]
+    fn parses_bare_array_from_requester() {
+        let requester = MockRequester::with_responses(vec![json!({
+            "status": 200,
+            "body": [{ "name": "anime", "count": 3 }],
+        })]);
+        let client = TissueClient::new("token", requester);
+
+        let tags = block_on(client.search_tags("an")).unwrap();
+        assert_eq!(tags.len(), 1);
+        assert_eq!(tags[0].name(), "anime");
+    }
+
+    #[test]
+    fn searches_tags() {
+        let requester = MockRequester::with_responses(vec![json!([
+            { "name": "anime", "count": 12 },
+            { "name": "animation", "count": 3 },
+            { "name": "anime_girl" },
+        ])]);
+        let client = TissueClient::new("token", requester.clone());
+
+        let tags = block_on(client.search_tags("ani me&")).unwrap();
+        let tags: Vec<_> = tags.iter().map(|tag| (tag.name(), tag.count())).collect();
+        assert_eq!(
+            tags,
+            vec![("anime", 12), ("animation", 3), ("anime_girl", 0)]
+        );
+
+        let requests = requester.requests();
+        assert_eq!(
+            requests[0].url(),
+            "https://shikorism.net/api/tags?q=ani%20me%26"
+        );
+        assert_eq!(requests[0].headers()["Authorization"], "Bearer token");
+    }
+
+    #[test]
+    fn fetches_stats() {
+        let requester = MockRequester::with_responses(vec![
+            json!({
+                "status": 200,
+                "total_checkins": 120,
+                "current_streak_days": 2,
+                "longest_streak_days": 9,
+                "average_interval_seconds": 86400,
+                "last_checked_in_at": "2021-01-01T00:00:00+09:00",
+                "unknown": true,
+            }),
+            json!({ "status": 200, "total_checkins": 120 }),
+        ]);
+        let client = TissueClient::new("token", requester.clone());
+
+        let stats = block_on(client.stats()).unwrap();
+        assert_eq!(stats.total_checkins(), Some(120));
+        assert_eq!(stats.current_streak_days(), Some(2));
+        assert_eq!(stats.longest_streak_days(), Some(9));
+        assert_eq!(stats.average_interval_seconds(), Some(86400));
+        assert!(stats.last_checked_in_at().is_some());
+        assert_eq!(
+            requester.requests()[0].url(),
+            "https://shikorism.net/api/user/stats"
+        );
+
+        let stats = block_on(client.stats()).unwrap();
+        assert_eq!(stats.total_checkins(), Some(120));
+        assert_eq!(stats.current_streak_days(), None);
+        assert!(stats.last_checked_in_at().is_none());
+    }
+
+    #[test]
+    fn sends_authorization_header() {
+        let requester = MockRequester::with_responses(vec![json!({ "status": 204 })]);
+        let client = TissueClient::with_domain("tissue.example.com", "token", requester.clone())
+            .with_header("X-Client", "test");
+
+        block_on(client.delete_checkin(1)).unwrap();
+        let requests = requester.requests();
+        assert_eq!(requests[0].headers()["Authorization"], "Bearer token");
+        assert_eq!(requests[0].headers()["X-Client"], "test");
+        assert!(requests[0].url().starts_with("https://tissue.example.com/"));
+    }
+
+    fn checkin_json(id: usize) -> Value {
+        json!({
+            "id": id,
+            "checked_in_at": "2021-01-01T00:00:00+09:00",
+            "note": "note",
+            "link": "",
+            "tags": ["anime"],
+            "source": "api",
+            "is_private": false,
+            "is_too_sensitive": false,
+        })
+    }
+
+    #[test]
+    fn creates_checkin() {
+        let mut response = checkin_json(1);
+        response["status"] = 201.into();
+        let requester = MockRequester::with_responses(vec![response]);
+        let client = TissueClient::new("token", requester.clone());
+
+        let mut builder = Checkin::builder();
+        builder.note("note").unwrap().source("app").unwrap();
+        let response = block_on(client.create_checkin(&builder.build())).unwrap();
+        let received = response.into_received().unwrap();
+        assert_eq!(received.id(), 1);
+        assert_eq!(received.note().map(String::as_str), Some("note"));
+
+        let requests = requester.requests();
+        assert_eq!(requests[0].method(), "POST");
+        assert_eq!(requests[0].url(), "https://shikorism.net/api/checkins");
+        assert_eq!(requests[0].body().unwrap()["note"], "note");
+    }
+
+    #[test]
+    fn lists_two_pages() {
+        let requester = MockRequester::with_responses(vec![
+            json!({
+                "status": 200,
+                "data": [checkin_json(1), checkin_json(2)],
+                "total": 3,
+                "last_page": 2,
+            }),
+            json!({
+                "status": 200,
+                "data": [checkin_json(3)],
+                "total": 3,
+                "last_page": 2,
+            }),
+        ]);
+        let client = TissueClient::new("token", requester.clone());
+
+        let first = block_on(client.list_checkins(1, 2)).unwrap();
+        assert_eq!(first.items().len(), 2);
+        assert_eq!(first.total(), Some(3));
+        assert_eq!(first.next_page(), Some(2));
+
+        let second = block_on(client.list_checkins(2, 2)).unwrap();
+        assert_eq!(second.items()[0].id(), 3);
+        assert_eq!(second.next_page(), None);
+
+        let urls: Vec<_> = requester
+            .requests()
+            .iter()
+            .map(|request| request.url().to_owned())
+            .collect();
+        assert_eq!(
+            urls,
+            vec![
+                "https://shikorism.net/api/checkins?page=1&per_page=2",
+                "https://shikorism.net/api/checkins?page=2&per_page=2",
+            ]
+        );
+    }
+
+    #[test]
+    fn gets_checkin() {
+        let mut found = checkin_json(7);
+        found["status"] = 200.into();
+        let requester = MockRequester::with_responses(vec![
+            found,
+            json!({ "status": 404, "message": "Not Found" }),
+        ]);
+        let client = TissueClient::new("token", requester.clone());
+
+        assert_eq!(block_on(client.get_checkin(7)).unwrap().id(), 7);
+        assert!(matches!(
+            block_on(client.get_checkin(8)),
+            Err(TissueError::NotFound)
+        ));
+        assert_eq!(
+            requester.requests()[1].url(),
+            "https://shikorism.net/api/checkins/8"
+        );
+    }
+
+    #[test]
+    fn deletes_checkin() {
+        let requester =
+            MockRequester::with_responses(vec![json!({ "status": 204 }), json!({ "status": 404 })]);
+        let client = TissueClient::new("token", requester.clone());
+
+        block_on(client.delete_checkin(1)).unwrap();
+        assert!(matches!(
+            block_on(client.delete_checkin(2)),
+            Err(TissueError::NotFound)
+        ));
+
+        let requests = requester.requests();
+        assert_eq!(requests[0].method(), "DELETE");
+        assert_eq!(requests[0].url(), "https://shikorism.net/api/checkins/1");
+        assert_eq!(requests[0].body(), None);
+    }
+
+    #[test]
+    fn updates_checkin() {
+        let mut response = checkin_json(1);
+        response["status"] = 200.into();
+        let requester = MockRequester::with_responses(vec![response]);
+        let client = TissueClient::new("token", requester.clone());
+
+        let mut builder = CheckinBuilder::<Utc>::from_unix_timestamp(0).unwrap();
+        builder.note("edited").unwrap().tags(["anime"]).unwrap();
+        let checkin = builder.build();
+        let response = block_on(client.update_checkin(1, &checkin)).unwrap();
+        assert!(response.is_success());
+
+        let requests = requester.requests();
+        assert_eq!(requests[0].method(), "PUT");
+        assert_eq!(requests[0].url(), "https://shikorism.net/api/checkins/1");
+        assert_eq!(requests[0].body(), Some(&checkin.to_api_value()));
+        assert_eq!(requests[0].body().unwrap()["note"], "edited");
+    }
+
+    #[test]
+    fn fetches_current_user() {
+        let requester = MockRequester::with_responses(vec![
+            json!({
+                "status": 200,
+                "id": 1,
+                "name": "kb10uy",
+                "display_name": "KOBAYASHI",
+                "bio": "bio",
+                "url": "https://kb10uy.org",
+                "checkins_count": 42,
+                "is_protected": false,
+            }),
+            json!({ "status": 200, "id": 2, "name": "minimal" }),
+        ]);
+        let client = TissueClient::new("token", requester.clone());
+
+        let user = block_on(client.current_user()).unwrap();
+        assert_eq!(user.id(), 1);
+        assert_eq!(user.name(), "kb10uy");
+        assert_eq!(user.display_name(), "KOBAYASHI");
+        assert_eq!(user.bio().map(String::as_str), Some("bio"));
+        assert_eq!(user.url().map(String::as_str), Some("https://kb10uy.org"));
+        assert_eq!(user.checkins_count(), Some(42));
+        assert_eq!(
+            requester.requests()[0].url(),
+            "https://shikorism.net/api/user"
+        );
+
+        let user = block_on(client.current_user()).unwrap();
+        assert_eq!(user.display_name(), "");
+        assert_eq!(user.checkins_count(), None);
+    }
+
+    #[test]
+    fn streams_checkins_across_pages() {
+        let requester = MockRequester::with_responses(vec![
+            json!({ "status": 200, "data": [checkin_json(1), checkin_json(2)] }),
+            json!({ "status": 200, "data": [checkin_json(3)] }),
+        ]);
+        let client = TissueClient::new("token", requester.clone());
+
+        let ids: Vec<_> = block_on(client.checkins_stream(2).collect::<Vec<_>>())
+            .into_iter()
+            .map(|checkin| checkin.unwrap().id())
+            .collect();
+        assert_eq!(ids, [1, 2, 3]);
+        // The short page ends the stream without fetching another
+        assert_eq!(requester.requests().len(), 2);
+    }
+
+    #[test]
+    fn lists_with_filter_query() {
+        let requester = MockRequester::with_responses(vec![json!({ "status": 200, "data": [] })]);
+        let client = TissueClient::new("token", requester.clone());
+        let jst = FixedOffset::east_opt(9 * 3600).unwrap();
+        let filter = CheckinFilter::new()
+            .with_since(Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap())
+            .with_until(jst.with_ymd_and_hms(2024, 2, 1, 0, 0, 0).unwrap())
+            .with_tag("a&b c");
+
+        block_on(client.list_checkins_filtered(1, 10, &filter)).unwrap();
+        assert_eq!(
+            requester.requests()[0].url(),
+            "https://shikorism.net/api/checkins?page=1&per_page=10\
+             &since=2024-01-01T00%3A00%3A00Z\
+             &until=2024-02-01T00%3A00%3A00%2B09%3A00\
+             &tag=a%26b%20c"
+        );
+    }
+
+    #[test]
+    fn counts_checkins_from_total() {
+        let requester = MockRequester::with_responses(vec![
+            json!({ "status": 200, "data": [checkin_json(1)], "total": 1234 }),
+            json!({ "status": 200, "body": [checkin_json(1)] }),
+        ]);
+        let client = TissueClient::new("token", requester.clone());
+
+        assert_eq!(block_on(client.checkin_count()).unwrap(), 1234);
+        assert_eq!(
+            requester.requests()[0].url(),
+            "https://shikorism.net/api/checkins?page=1&per_page=1"
+        );
+        // A bare array has no total
+        assert!(matches!(
+            block_on(client.checkin_count()),
+            Err(TissueError::MalformedResponse(_))
+        ));
+    }
+}
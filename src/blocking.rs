@@ -0,0 +1,18 @@
+//! Contains blocking wrappers of async APIs.
+
+use crate::{
+    checkin::Checkin,
+    error::TissueError,
+    tissue::{CheckinResponse, IncomingEndpoint},
+    TissueRequester,
+};
+
+use futures::executor::block_on;
+
+impl<T: TissueRequester> IncomingEndpoint<T> {
+    /// Sends a checkin, blocking the current thread until it completes.
+    /// The requester must not depend on an async runtime context (e.g. `ReqwestRequester` needs Tokio).
+    pub fn send_checkin_blocking(&self, checkin: &Checkin) -> Result<CheckinResponse, TissueError> {
+        block_on(self.send_checkin(checkin))
+    }
+}
@@ -1,23 +1,153 @@
 //! Contains checkin types.
 
-use crate::error::CheckinError;
-use std::fmt::Display;
+use crate::{
+    error::{CheckinError, TissueError},
+    tissue::{CheckinResponse, IncomingEndpoint, ReceivedCheckin},
+    TissueRequester,
+};
+use std::{
+    borrow::Cow,
+    collections::HashSet,
+    fmt::{Debug, Display, Formatter, Result as FmtResult},
+};
 
-use chrono::prelude::*;
-use serde::Serialize;
+use chrono::{prelude::*, Duration};
+use serde::{Deserialize, Serialize};
+use serde_json::{to_value, Value};
+#[cfg(feature = "unicode-normalization")]
+use unicode_normalization::{is_nfc, UnicodeNormalization};
+#[cfg(feature = "unicode-segmentation")]
+use unicode_segmentation::UnicodeSegmentation;
+#[cfg(feature = "url")]
+use url::Url;
+
+/// Maximum length of checkin note in characters, same as Tissue.
+/// Tissue counts code points, so with `unicode-segmentation` feature a note within this limit
+/// may still be rejected (e.g. 500 flag emoji are 1000 code points).
+pub const NOTE_MAX_CHARS: usize = 500;
+
+/// Maximum length of checkin link in characters, same as Tissue.
+pub const LINK_MAX_CHARS: usize = 2000;
+
+/// Maximum number of tags in a checkin, after the `tags` rule (`max:40`) of Tissue checkin
+/// validation. It is taken as an assumption rather than checked for every Tissue version,
+/// so an instance may still reject fewer tags.
+pub const MAX_TAGS: usize = 40;
+
+/// Maximum length of checkin source in characters.
+pub const SOURCE_MAX_CHARS: usize = 255;
+
+/// Length of note shown in `Display` of `Checkin`, in characters.
+const DISPLAY_NOTE_CHARS: usize = 20;
+
+/// Source of the current time for `CheckinBuilder`, replaceable for deterministic tests.
+/// Closures returning `DateTime<Utc>` can be used as clocks.
+pub trait Clock {
+    /// Returns the current time.
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// `Clock` reading the system time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+impl<F: Fn() -> DateTime<Utc>> Clock for F {
+    fn now(&self) -> DateTime<Utc> {
+        self()
+    }
+}
+
+/// Describes an adjustment made to a tag by `CheckinBuilder`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum TagWarning {
+    /// The tag was trimmed, had a leading `#` removed, or was normalized
+    Altered {
+        /// Position in the given tags
+        index: usize,
+
+        /// Tag as given
+        original: String,
+
+        /// Tag as accepted
+        tag: String,
+    },
+
+    /// The tag was empty after trimming, and dropped
+    Dropped {
+        /// Position in the given tags
+        index: usize,
+
+        /// Tag as given
+        original: String,
+    },
+}
 
 /// Describes a valid checkin.
-#[derive(Debug, PartialEq, Eq, Hash, Serialize)]
+/// `Debug` shows only the lengths of note and link unless `full-debug` feature is enabled.
+/// `Display` is not redacted and shows the first line of note, so avoid it in shared logs.
+#[derive(Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "full-debug", derive(Debug))]
 pub struct Checkin {
     checked_in_at: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
     note: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     link: Option<String>,
+    #[serde(default)]
     tags: Box<[String]>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     is_private: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     is_too_sensitive: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    source: Option<String>,
 }
 
 impl Checkin {
+    /// Creates a new builder with local timezone, same as `CheckinBuilder::new_local`.
+    ///
+    /// ```
+    /// use tissue_rs::Checkin;
+    ///
+    /// let checkin = Checkin::builder().build();
+    /// assert_eq!(checkin.note(), None);
+    /// ```
+    pub fn builder() -> CheckinBuilder<Local> {
+        CheckinBuilder::<Local>::new_local()
+    }
+
+    /// Creates a new builder with UTC, same as `CheckinBuilder::new_utc`.
+    pub fn builder_utc() -> CheckinBuilder<Utc> {
+        CheckinBuilder::<Utc>::new_utc()
+    }
+
+    /// Sends this checkin to the endpoint, same as `IncomingEndpoint::send_checkin`.
+    ///
+    /// ```
+    /// use tissue_rs::{Checkin, IncomingEndpoint, TissueError, TissueRequester};
+    ///
+    /// async fn checkin_now<T: TissueRequester>(
+    ///     endpoint: &IncomingEndpoint<T>,
+    /// ) -> Result<(), TissueError> {
+    ///     let checkin = Checkin::builder().build();
+    ///     checkin.send(endpoint).await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn send<T: TissueRequester>(
+        &self,
+        endpoint: &IncomingEndpoint<T>,
+    ) -> Result<CheckinResponse, TissueError> {
+        endpoint.send_checkin(self).await
+    }
+
     /// Timestamp of checkin.
     pub fn checked_in_at(&self) -> &str {
         &self.checked_in_at
@@ -47,10 +177,83 @@ impl Checkin {
     pub fn is_too_sensitive(&self) -> Option<bool> {
         self.is_too_sensitive
     }
+
+    /// Source.
+    pub fn source(&self) -> Option<&String> {
+        self.source.as_ref()
+    }
+
+    /// Renders into the JSON body for Incoming Webhook.
+    /// Tissue validates the same field names for the webhook and the authenticated API
+    /// (`checked_in_at`, `note`, `link`, `tags`, `is_private` and `is_too_sensitive`),
+    /// so the two bodies differ only in `source`. They are kept as separate methods so that
+    /// callers do not depend on it if the server ever diverges.
+    pub fn to_webhook_value(&self) -> Value {
+        to_value(self).expect("Checkin should be serialized")
+    }
+
+    /// Renders into the JSON body for the authenticated API.
+    /// `source` is omitted, since the API sets it by itself.
+    pub fn to_api_value(&self) -> Value {
+        let mut value = self.to_webhook_value();
+        if let Value::Object(object) = &mut value {
+            object.remove("source");
+        }
+        value
+    }
+}
+
+#[cfg(not(feature = "full-debug"))]
+impl Debug for Checkin {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("Checkin")
+            .field("checked_in_at", &self.checked_in_at)
+            .field("note", &Redacted(self.note.as_deref()))
+            .field("link", &Redacted(self.link.as_deref()))
+            .field("tags", &self.tags)
+            .field("is_private", &self.is_private)
+            .field("is_too_sensitive", &self.is_too_sensitive)
+            .field("source", &self.source)
+            .finish()
+    }
+}
+
+impl Display for Checkin {
+    /// Formats a one-line summary like `2024-01-02T03:04:05Z "cyan.png" #anime #fav`.
+    /// Only the first line of note is shown, and `...` is appended if the line is longer than
+    /// 20 characters or more lines follow.
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{}", self.checked_in_at)?;
+        if let Some(note) = self.note.as_deref().filter(|n| !n.is_empty()) {
+            let mut lines = note.lines();
+            let first_line = lines.next().unwrap_or("");
+            let truncated: String = first_line.chars().take(DISPLAY_NOTE_CHARS).collect();
+            let omitted =
+                truncated.len() < first_line.len() || lines.any(|line| !line.trim().is_empty());
+            if omitted {
+                write!(f, " \"{}...\"", truncated)?;
+            } else {
+                write!(f, " \"{}\"", truncated)?;
+            }
+        }
+        for tag in self.tags.iter() {
+            write!(f, " #{}", tag)?;
+        }
+        Ok(())
+    }
+}
+
+impl From<&ReceivedCheckin> for Checkin {
+    /// Converts a received checkin for resubmission, in the same way as `CheckinBuilder::from_received`.
+    fn from(received: &ReceivedCheckin) -> Checkin {
+        CheckinBuilder::<Local>::from_received(received).build()
+    }
 }
 
 /// Builder for `Checkin`.
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// `Debug` is redacted in the same way as `Checkin`.
+#[derive(Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "full-debug", derive(Debug))]
 pub struct CheckinBuilder<Tz: TimeZone>
 where
     <Tz as TimeZone>::Offset: Display,
@@ -61,6 +264,15 @@ where
     tags: Vec<String>,
     is_private: Option<bool>,
     is_too_sensitive: Option<bool>,
+    source: Option<String>,
+    seconds_format: SecondsFormat,
+    future_tolerance: Duration,
+    note_limit: usize,
+    strict_tags: bool,
+    tag_warnings: Vec<TagWarning>,
+    now: Option<DateTime<Utc>>,
+    #[cfg(feature = "unicode-normalization")]
+    normalize_nfc: bool,
 }
 
 impl<Tz: TimeZone> CheckinBuilder<Tz>
@@ -69,25 +281,31 @@ where
 {
     /// Creates a new builder with local timezone.
     pub fn new_local() -> CheckinBuilder<Local> {
-        CheckinBuilder {
-            checked_in_at: Local::now(),
-            note: None,
-            link: None,
-            tags: vec![],
-            is_private: None,
-            is_too_sensitive: None,
-        }
+        CheckinBuilder::with_datetime(SystemClock.now().with_timezone(&Local))
     }
 
     /// Creates a new builder with UTC.
     pub fn new_utc() -> CheckinBuilder<Utc> {
+        CheckinBuilder::with_datetime(SystemClock.now())
+    }
+
+    /// Creates a new builder with current time of `clock` in `timezone`.
+    /// The time is read once, and `try_build()` checks future timestamps against it
+    /// instead of the system time.
+    ///
+    /// ```
+    /// use chrono::prelude::*;
+    /// use tissue_rs::CheckinBuilder;
+    ///
+    /// let fixed = || Utc.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap();
+    /// let builder = CheckinBuilder::new_with_clock(&fixed, &Utc);
+    /// assert_eq!(builder.build().checked_in_at(), "2024-01-02T03:04:05Z");
+    /// ```
+    pub fn new_with_clock<C: Clock + ?Sized>(clock: &C, timezone: &Tz) -> CheckinBuilder<Tz> {
+        let now = clock.now();
         CheckinBuilder {
-            checked_in_at: Utc::now(),
-            note: None,
-            link: None,
-            tags: vec![],
-            is_private: None,
-            is_too_sensitive: None,
+            now: Some(now),
+            ..CheckinBuilder::with_datetime(now.with_timezone(timezone))
         }
     }
 
@@ -100,75 +318,1234 @@ where
             tags: vec![],
             is_private: None,
             is_too_sensitive: None,
+            source: None,
+            seconds_format: SecondsFormat::Secs,
+            future_tolerance: Duration::minutes(5),
+            note_limit: NOTE_MAX_CHARS,
+            strict_tags: false,
+            tag_warnings: vec![],
+            now: None,
+            #[cfg(feature = "unicode-normalization")]
+            normalize_nfc: true,
         }
     }
 
+    /// Timestamp of checkin.
+    pub fn checked_in_at(&self) -> &DateTime<Tz> {
+        &self.checked_in_at
+    }
+
     /// Sets checkin note.
-    /// Returns `Err(CheckinError::TooLong)` if `text` >= 2000 bytes.
-    pub fn note(&mut self, text: &str) -> Result<(), CheckinError> {
-        if text.chars().count() <= 500 {
-            self.note = Some(text.into());
-            Ok(())
-        } else {
-            Err(CheckinError::TooLong)
-        }
+    /// Returns `Err(CheckinError::TooLong { .. })` if `text` exceeds the note limit,
+    /// `NOTE_MAX_CHARS` characters by default.
+    /// Returns `Err(CheckinError::InvalidCharacter)` if it has control characters
+    /// other than `\n`, `\r` and `\t`.
+    /// With `unicode-segmentation` feature, characters are counted as grapheme clusters.
+    /// It is looser than Tissue counting code points, so the server may still reject the note.
+    /// With `unicode-normalization` feature, it is normalized into NFC before counting.
+    pub fn note(&mut self, text: &str) -> Result<&mut Self, CheckinError> {
+        let text = self.normalize(text);
+        check_length("note", note_length(&text), self.note_limit)?;
+        check_characters(&text)?;
+        self.note = Some(text.into_owned());
+        Ok(self)
     }
 
     /// Sets checkin link.
-    /// Returns `Err(CheckinError::TooLong)` if `text` >= 500 bytes.
-    pub fn link(&mut self, link: &str) -> Result<(), CheckinError> {
-        if link.chars().count() <= 2000 {
-            self.link = Some(link.into());
-            Ok(())
-        } else {
-            Err(CheckinError::TooLong)
-        }
+    /// Returns `Err(CheckinError::TooLong { .. })` if `link` exceeds `LINK_MAX_CHARS` characters.
+    /// With `url` feature, returns `Err(CheckinError::InvalidUrl)` if `link` is not
+    /// an absolute http(s) URL.
+    pub fn link(&mut self, link: &str) -> Result<&mut Self, CheckinError> {
+        check_length("link", char_length(link), LINK_MAX_CHARS)?;
+        check_url(link)?;
+        self.link = Some(link.into());
+        Ok(self)
     }
 
-    /// Sets tags. For each tag, leading/trailing whitespaces will be removed.
-    /// Returns `Err(CheckinError::HasWhitespaces)` if whitespaces found in the middle.
+    /// Sets checkin source.
+    /// Returns `Err(CheckinError::TooLong { .. })` if `source` exceeds `SOURCE_MAX_CHARS` characters.
+    pub fn source(&mut self, source: &str) -> Result<&mut Self, CheckinError> {
+        check_length("source", char_length(source), SOURCE_MAX_CHARS)?;
+        self.source = Some(source.into());
+        Ok(self)
+    }
+
+    /// Sets tags. For each tag, leading/trailing whitespaces and a leading `#` will be removed.
+    /// Returns `Err(CheckinError::HasWhitespaces)` if whitespaces found in the middle,
+    /// `Err(CheckinError::InvalidTag)` for a leading `#` in strict mode,
+    /// or `Err(CheckinError::TooManyTags)` if more than `MAX_TAGS` tags are given.
     pub fn tags<T: AsRef<str>, I: IntoIterator<Item = T>>(
         &mut self,
         tags: I,
-    ) -> Result<(), CheckinError> {
+    ) -> Result<&mut Self, CheckinError> {
         let mut validated = vec![];
-        for tag in tags {
-            let tag_str = tag.as_ref().trim();
-            if tag_str == "" {
-                continue;
+        let mut warnings = vec![];
+        for (index, tag) in tags.into_iter().enumerate() {
+            if let Some(tag_str) = self.accept_tag(index, tag.as_ref(), &mut warnings)? {
+                validated.push(tag_str);
             }
+        }
+        if validated.len() > MAX_TAGS {
+            return Err(CheckinError::TooManyTags);
+        }
 
-            if tag_str.chars().any(|c| c.is_whitespace()) {
-                return Err(CheckinError::HasWhitespaces);
-            } else {
-                validated.push(tag_str.to_owned());
+        self.tags = validated;
+        self.tag_warnings = warnings;
+        Ok(self)
+    }
+
+    /// Sets tags from a string delimited by `sep`, like `"anime, fav, cyan"` with `','`.
+    /// Each tag is trimmed and validated in the same way as `tags`.
+    pub fn tags_from_str(&mut self, tags: &str, sep: char) -> Result<&mut Self, CheckinError> {
+        self.tags(tags.split(sep))
+    }
+
+    /// Adds a tag after existing ones. Leading/trailing whitespaces and a leading `#`
+    /// will be removed, and an empty tag will be ignored.
+    /// Returns `Err(CheckinError::HasWhitespaces)` if whitespaces found in the middle,
+    /// `Err(CheckinError::InvalidTag)` for a leading `#` in strict mode,
+    /// or `Err(CheckinError::TooManyTags)` if it already has `MAX_TAGS` tags.
+    pub fn add_tag(&mut self, tag: &str) -> Result<&mut Self, CheckinError> {
+        let mut warnings = vec![];
+        if let Some(tag_str) = self.accept_tag(0, tag, &mut warnings)? {
+            if self.tags.len() >= MAX_TAGS {
+                return Err(CheckinError::TooManyTags);
             }
+            self.tags.push(tag_str);
         }
 
-        Ok(())
+        self.tag_warnings = warnings;
+        Ok(self)
+    }
+
+    /// Adjustments made to the tags by the last successful `tags`, `tags_from_str` or `add_tag`.
+    /// For `add_tag`, `index` is always 0.
+    ///
+    /// ```
+    /// use tissue_rs::{Checkin, TagWarning};
+    ///
+    /// let mut builder = Checkin::builder();
+    /// builder.tags(&[" a ", "", "b"]).unwrap();
+    /// let warnings = builder.last_tag_warnings();
+    /// assert!(matches!(&warnings[0], TagWarning::Altered { index: 0, tag, .. } if tag == "a"));
+    /// assert!(matches!(&warnings[1], TagWarning::Dropped { index: 1, .. }));
+    /// assert_eq!(warnings.len(), 2);
+    /// ```
+    pub fn last_tag_warnings(&self) -> &[TagWarning] {
+        &self.tag_warnings
+    }
+
+    /// Removes duplicated tags, keeping the first occurrence.
+    pub fn dedup_tags(&mut self) -> &mut Self {
+        let mut seen = HashSet::new();
+        self.tags.retain(|tag| seen.insert(tag.clone()));
+        self
+    }
+
+    /// Removes duplicated tags ignoring case, keeping the first occurrence.
+    pub fn dedup_tags_ignore_case(&mut self) -> &mut Self {
+        let mut seen = HashSet::new();
+        self.tags.retain(|tag| seen.insert(tag.to_lowercase()));
+        self
+    }
+
+    /// Clears checkin note.
+    pub fn clear_note(&mut self) -> &mut Self {
+        self.note = None;
+        self
+    }
+
+    /// Clears checkin link.
+    pub fn clear_link(&mut self) -> &mut Self {
+        self.link = None;
+        self
+    }
+
+    /// Clears all tags.
+    pub fn clear_tags(&mut self) -> &mut Self {
+        self.tags.clear();
+        self
     }
 
     /// Sets private flag.
-    pub fn is_private(&mut self, is_private: bool) {
+    pub fn is_private(&mut self, is_private: bool) -> &mut Self {
         self.is_private = Some(is_private);
+        self
     }
 
     /// Sets too-sensitive flag.
-    pub fn is_too_sensitive(&mut self, is_too_sensitive: bool) {
+    pub fn is_too_sensitive(&mut self, is_too_sensitive: bool) -> &mut Self {
         self.is_too_sensitive = Some(is_too_sensitive);
+        self
     }
 
-    /// Builds `Checkin`.
+    /// Sets the precision of the timestamp formatted in `build()`.
+    /// Defaults to `SecondsFormat::Secs`.
+    pub fn seconds_format(&mut self, format: SecondsFormat) -> &mut Self {
+        self.seconds_format = format;
+        self
+    }
+
+    /// Floors the timestamp to the minute, dropping seconds and fractions.
+    /// It applies before `seconds_format`, so the seconds are formatted as zero in any precision.
+    ///
+    /// ```
+    /// use tissue_rs::CheckinBuilder;
+    ///
+    /// let mut builder = CheckinBuilder::with_rfc3339("2024-01-02T03:04:05.678+09:00").unwrap();
+    /// builder.round_to_minute();
+    /// assert_eq!(builder.build().checked_in_at(), "2024-01-02T03:04:00+09:00");
+    /// ```
+    pub fn round_to_minute(&mut self) -> &mut Self {
+        let excess = Duration::seconds(self.checked_in_at.second().into())
+            + Duration::nanoseconds(self.checked_in_at.nanosecond().into());
+        self.checked_in_at = self.checked_in_at.clone() - excess;
+        self
+    }
+
+    /// Sets how far `checked_in_at` may be ahead of the current time in `try_build()`.
+    /// Defaults to 5 minutes.
+    pub fn future_tolerance(&mut self, tolerance: Duration) -> &mut Self {
+        self.future_tolerance = tolerance;
+        self
+    }
+
+    /// Sets the maximum length of note in characters, for instances with a custom limit.
+    /// Defaults to `NOTE_MAX_CHARS`.
+    pub fn note_limit(&mut self, limit: usize) -> &mut Self {
+        self.note_limit = limit;
+        self
+    }
+
+    /// Sets whether tags with a leading `#`, like `#anime`, are rejected
+    /// instead of it being removed.
+    /// Defaults to `false`, and affects only tags set after this.
+    pub fn strict_tags(&mut self, strict: bool) -> &mut Self {
+        self.strict_tags = strict;
+        self
+    }
+
+    /// Sets whether note and tags set after this are normalized into NFC. Defaults to `true`.
+    #[cfg(feature = "unicode-normalization")]
+    pub fn normalize_nfc(&mut self, normalize: bool) -> &mut Self {
+        self.normalize_nfc = normalize;
+        self
+    }
+
+    /// Validates all fields again and builds `Checkin`.
+    /// Returns every violation found, including `CheckinError::FutureTimestamp` if
+    /// `checked_in_at` is ahead of the current time by more than the future tolerance.
+    /// Prefer this to `build()`, which skips the checks.
+    pub fn try_build(self) -> Result<Checkin, Vec<CheckinError>> {
+        let errors = self.validate();
+        if errors.is_empty() {
+            Ok(self.build())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Builds `Checkin` without validation.
     pub fn build(self) -> Checkin {
         Checkin {
             checked_in_at: self
                 .checked_in_at
-                .to_rfc3339_opts(SecondsFormat::Secs, true),
+                .to_rfc3339_opts(self.seconds_format, true),
             note: self.note,
             link: self.link,
             tags: self.tags.into_boxed_slice(),
             is_private: self.is_private,
             is_too_sensitive: self.is_too_sensitive,
+            source: self.source,
+        }
+    }
+
+    /// Normalizes text into NFC unless disabled.
+    #[cfg(feature = "unicode-normalization")]
+    fn normalize<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        if self.normalize_nfc && !is_nfc(text) {
+            Cow::Owned(text.nfc().collect())
+        } else {
+            Cow::Borrowed(text)
+        }
+    }
+
+    /// Returns text as is without `unicode-normalization` feature.
+    #[cfg(not(feature = "unicode-normalization"))]
+    fn normalize<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        Cow::Borrowed(text)
+    }
+
+    /// Normalizes and validates a tag, recording the adjustment if any.
+    fn accept_tag(
+        &self,
+        index: usize,
+        tag: &str,
+        warnings: &mut Vec<TagWarning>,
+    ) -> Result<Option<String>, CheckinError> {
+        let accepted = validate_tag(&self.normalize(tag), self.strict_tags)?;
+        match &accepted {
+            Some(tag_str) if tag_str != tag => warnings.push(TagWarning::Altered {
+                index,
+                original: tag.into(),
+                tag: tag_str.clone(),
+            }),
+            Some(_) => (),
+            None => warnings.push(TagWarning::Dropped {
+                index,
+                original: tag.into(),
+            }),
+        }
+
+        Ok(accepted)
+    }
+
+    /// Runs all checks for current fields.
+    fn validate(&self) -> Vec<CheckinError> {
+        let mut errors = vec![];
+        if let Some(note) = &self.note {
+            errors.extend(check_length("note", note_length(note), self.note_limit).err());
+            errors.extend(check_characters(note).err());
+        }
+        if let Some(link) = &self.link {
+            errors.extend(check_length("link", char_length(link), LINK_MAX_CHARS).err());
+            errors.extend(check_url(link).err());
+        }
+        if let Some(source) = &self.source {
+            errors.extend(check_length("source", char_length(source), SOURCE_MAX_CHARS).err());
+        }
+        // Leading `#` was already handled with the strictness when each tag was set
+        errors.extend(
+            self.tags
+                .iter()
+                .filter_map(|tag| validate_tag(tag, false).err()),
+        );
+        if self.tags.len() > MAX_TAGS {
+            errors.push(CheckinError::TooManyTags);
+        }
+
+        let now = self.now.unwrap_or_else(|| SystemClock.now());
+        let ahead = self.checked_in_at.clone().signed_duration_since(now);
+        if ahead > self.future_tolerance {
+            errors.push(CheckinError::FutureTimestamp);
         }
+
+        errors
+    }
+}
+
+#[cfg(not(feature = "full-debug"))]
+impl<Tz: TimeZone> Debug for CheckinBuilder<Tz>
+where
+    <Tz as TimeZone>::Offset: Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let mut debug = f.debug_struct("CheckinBuilder");
+        debug
+            .field("checked_in_at", &self.checked_in_at)
+            .field("note", &Redacted(self.note.as_deref()))
+            .field("link", &Redacted(self.link.as_deref()))
+            .field("tags", &self.tags)
+            .field("is_private", &self.is_private)
+            .field("is_too_sensitive", &self.is_too_sensitive)
+            .field("source", &self.source)
+            .field("seconds_format", &self.seconds_format)
+            .field("future_tolerance", &self.future_tolerance)
+            .field("note_limit", &self.note_limit)
+            .field("strict_tags", &self.strict_tags)
+            .field("tag_warnings", &self.tag_warnings)
+            .field("now", &self.now);
+        #[cfg(feature = "unicode-normalization")]
+        debug.field("normalize_nfc", &self.normalize_nfc);
+        debug.finish()
+    }
+}
+
+impl CheckinBuilder<Local> {
+    /// Creates a new builder pre-populated from a received checkin.
+    /// Empty note and link are treated as unset, and source is not carried over.
+    pub fn from_received(received: &ReceivedCheckin) -> CheckinBuilder<Local> {
+        let non_empty = |s: Option<&String>| s.filter(|s| !s.is_empty()).cloned();
+        let mut builder = CheckinBuilder::<Local>::with_datetime(*received.checked_in_at());
+        builder.note = non_empty(received.note());
+        builder.link = non_empty(received.link());
+        builder.tags = received.tags().cloned().collect();
+        builder.is_private = Some(received.is_private());
+        builder.is_too_sensitive = Some(received.is_too_sensitive());
+        builder
+    }
+}
+
+impl CheckinBuilder<Utc> {
+    /// Creates a new builder with Unix timestamp in seconds.
+    /// Returns `Err(CheckinError::OutOfRange)` if `secs` cannot be represented.
+    pub fn from_unix_timestamp(secs: i64) -> Result<CheckinBuilder<Utc>, CheckinError> {
+        let checked_in_at = Utc
+            .timestamp_opt(secs, 0)
+            .single()
+            .ok_or(CheckinError::OutOfRange)?;
+        Ok(CheckinBuilder::<Utc>::with_datetime(checked_in_at))
+    }
+}
+
+impl CheckinBuilder<FixedOffset> {
+    /// Creates a new builder with RFC 3339 timestamp like `2024-01-02T03:04:05+09:00`.
+    /// Returns `Err(CheckinError::Parse)` if it cannot be parsed.
+    pub fn with_rfc3339(timestamp: &str) -> Result<CheckinBuilder<FixedOffset>, CheckinError> {
+        let checked_in_at = DateTime::parse_from_rfc3339(timestamp).map_err(CheckinError::Parse)?;
+        Ok(CheckinBuilder::<FixedOffset>::with_datetime(checked_in_at))
+    }
+
+    /// Creates a new builder with current time in UTC offset of `hours`, like `9` for JST.
+    /// Returns `Err(CheckinError::OutOfRange)` unless it is from -12 to +14.
+    ///
+    /// ```
+    /// use tissue_rs::CheckinBuilder;
+    ///
+    /// let checkin = CheckinBuilder::with_offset_hours(9).unwrap().build();
+    /// assert!(checkin.checked_in_at().ends_with("+09:00"));
+    /// ```
+    pub fn with_offset_hours(hours: i32) -> Result<CheckinBuilder<FixedOffset>, CheckinError> {
+        if !(-12..=14).contains(&hours) {
+            return Err(CheckinError::OutOfRange);
+        }
+        let offset = FixedOffset::east_opt(hours * 3600).ok_or(CheckinError::OutOfRange)?;
+        Ok(CheckinBuilder::<FixedOffset>::with_datetime(
+            Utc::now().with_timezone(&offset),
+        ))
+    }
+
+    /// Creates a new builder with local date and time in known offset,
+    /// without relying on the timezone detection of the system.
+    /// Returns `Err(CheckinError::OutOfRange)` if it cannot be represented.
+    pub fn with_naive_local(
+        naive: NaiveDateTime,
+        offset: FixedOffset,
+    ) -> Result<CheckinBuilder<FixedOffset>, CheckinError> {
+        let checked_in_at = offset
+            .from_local_datetime(&naive)
+            .single()
+            .ok_or(CheckinError::OutOfRange)?;
+        Ok(CheckinBuilder::<FixedOffset>::with_datetime(checked_in_at))
+    }
+}
+
+impl Default for CheckinBuilder<Local> {
+    fn default() -> CheckinBuilder<Local> {
+        CheckinBuilder::<Local>::new_local()
+    }
+}
+
+/// Counts the length of note in user-perceived characters.
+#[cfg(feature = "unicode-segmentation")]
+fn note_length(text: &str) -> usize {
+    text.graphemes(true).count()
+}
+
+/// Counts the length of note in characters.
+#[cfg(not(feature = "unicode-segmentation"))]
+fn note_length(text: &str) -> usize {
+    char_length(text)
+}
+
+/// Sensitive text shown only by its length in `Debug`.
+#[cfg(not(feature = "full-debug"))]
+pub(crate) struct Redacted<'a>(pub(crate) Option<&'a str>);
+
+#[cfg(not(feature = "full-debug"))]
+impl Debug for Redacted<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self.0 {
+            Some(text) => write!(f, "Some(<redacted {} chars>)", char_length(text)),
+            None => write!(f, "None"),
+        }
+    }
+}
+
+/// Counts the length of text in characters.
+fn char_length(text: &str) -> usize {
+    text.chars().count()
+}
+
+/// Checks the length in characters.
+fn check_length(field: &'static str, actual: usize, limit: usize) -> Result<(), CheckinError> {
+    if actual <= limit {
+        Ok(())
+    } else {
+        Err(CheckinError::TooLong {
+            field,
+            limit,
+            actual,
+        })
+    }
+}
+
+/// Checks that the text has no control characters other than newlines and tabs.
+fn check_characters(text: &str) -> Result<(), CheckinError> {
+    let disallowed = |c: char| c.is_control() && !matches!(c, '\n' | '\r' | '\t');
+    if text.chars().any(disallowed) {
+        Err(CheckinError::InvalidCharacter)
+    } else {
+        Ok(())
+    }
+}
+
+/// Checks that the link is an absolute http(s) URL.
+#[cfg(feature = "url")]
+fn check_url(link: &str) -> Result<(), CheckinError> {
+    match Url::parse(link) {
+        Ok(url) if url.scheme() == "http" || url.scheme() == "https" => Ok(()),
+        _ => Err(CheckinError::InvalidUrl),
+    }
+}
+
+/// Accepts any link without `url` feature.
+#[cfg(not(feature = "url"))]
+fn check_url(_link: &str) -> Result<(), CheckinError> {
+    Ok(())
+}
+
+/// Trims a tag and checks it. Returns `Ok(None)` for an empty tag.
+/// A leading `#` is removed, or rejected if `strict`.
+fn validate_tag(tag: &str, strict: bool) -> Result<Option<String>, CheckinError> {
+    let mut tag_str = tag.trim();
+    if let Some(stripped) = tag_str.strip_prefix('#') {
+        if strict {
+            return Err(CheckinError::InvalidTag);
+        }
+        tag_str = stripped;
+    }
+    if tag_str.is_empty() {
+        return Ok(None);
+    }
+
+    if tag_str.chars().any(|c| c.is_whitespace()) {
+        Err(CheckinError::HasWhitespaces)
+    } else {
+        Ok(Some(tag_str.to_owned()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn with_note(note: &str) -> Checkin {
+        let mut builder = CheckinBuilder::<Utc>::from_unix_timestamp(0).unwrap();
+        builder.note(note).unwrap();
+        builder.build()
+    }
+
+    #[test]
+    fn display_truncates_note() {
+        assert!(with_note("abc").to_string().ends_with(" \"abc\""));
+        assert!(with_note("abc\n").to_string().ends_with(" \"abc\""));
+        assert!(with_note("abc\ndef").to_string().ends_with(" \"abc...\""));
+        assert!(with_note("abcdefghijklmnopqrst")
+            .to_string()
+            .ends_with(" \"abcdefghijklmnopqrst\""));
+        assert!(with_note("abcdefghijklmnopqrstu")
+            .to_string()
+            .ends_with(" \"abcdefghijklmnopqrst...\""));
+    }
+
+    #[test]
+    fn display_formats_summary() {
+        let mut builder =
+            CheckinBuilder::with_datetime(Utc.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap());
+        builder
+            .note("cyan.png")
+            .unwrap()
+            .tags(["anime", "fav"])
+            .unwrap();
+        assert_eq!(
+            builder.build().to_string(),
+            "2024-01-02T03:04:05Z \"cyan.png\" #anime #fav"
+        );
+
+        let builder = CheckinBuilder::<Utc>::from_unix_timestamp(0).unwrap();
+        assert_eq!(builder.build().to_string(), "1970-01-01T00:00:00Z");
+    }
+
+    #[test]
+    fn future_timestamp_uses_clock() {
+        let future = Utc.with_ymd_and_hms(2100, 1, 1, 0, 0, 0).unwrap();
+        let builder = CheckinBuilder::new_with_clock(&|| future, &Utc);
+        assert!(builder.try_build().is_ok());
+
+        let builder = CheckinBuilder::with_datetime(future);
+        assert!(matches!(
+            builder.try_build().unwrap_err()[..],
+            [CheckinError::FutureTimestamp]
+        ));
+    }
+
+    #[test]
+    fn leading_hash_is_stripped() {
+        let mut builder = Checkin::builder();
+        builder.tags(["#anime", "##fav", "cyan"]).unwrap();
+        builder.add_tag("#png").unwrap();
+        let checkin = builder.try_build().unwrap();
+        assert_eq!(
+            checkin.tags().collect::<Vec<_>>(),
+            vec!["anime", "#fav", "cyan", "png"]
+        );
+    }
+
+    #[test]
+    fn leading_hash_is_rejected_in_strict_mode() {
+        let mut builder = Checkin::builder();
+        builder.strict_tags(true);
+        assert_eq!(
+            builder.tags(["#anime"]).unwrap_err(),
+            CheckinError::InvalidTag
+        );
+        assert_eq!(
+            builder.add_tag("##anime").unwrap_err(),
+            CheckinError::InvalidTag
+        );
+        builder.add_tag("anime").unwrap();
+        assert!(builder.try_build().is_ok());
+    }
+
+    #[test]
+    fn strict_mode_affects_only_later_tags() {
+        let mut builder = Checkin::builder();
+        builder.tags(["#a"]).unwrap();
+        builder.strict_tags(true);
+        let checkin = builder.try_build().unwrap();
+        assert_eq!(checkin.tags().collect::<Vec<_>>(), vec!["a"]);
+    }
+
+    #[test]
+    fn note_length_counts_emoji() {
+        let flag = "\u{1f1ef}\u{1f1f5}";
+        let family = "\u{1f468}\u{200d}\u{1f469}\u{200d}\u{1f467}";
+        #[cfg(feature = "unicode-segmentation")]
+        {
+            assert_eq!(note_length(flag), 1);
+            assert_eq!(note_length(family), 1);
+        }
+        #[cfg(not(feature = "unicode-segmentation"))]
+        {
+            assert_eq!(note_length(flag), 2);
+            assert_eq!(note_length(family), 5);
+        }
+
+        let mut builder = Checkin::builder();
+        builder.note_limit(note_length(flag) * 2);
+        assert!(builder.note(&flag.repeat(2)).is_ok());
+        assert!(matches!(
+            builder.note(&flag.repeat(3)),
+            Err(CheckinError::TooLong { field: "note", .. })
+        ));
+    }
+
+    #[test]
+    fn webhook_and_api_keys() {
+        let mut builder = CheckinBuilder::<Utc>::from_unix_timestamp(0).unwrap();
+        builder
+            .note("note")
+            .unwrap()
+            .link("https://example.com")
+            .unwrap()
+            .source("app")
+            .unwrap()
+            .tags(["anime"])
+            .unwrap()
+            .is_private(true)
+            .is_too_sensitive(false);
+        let checkin = builder.build();
+
+        let keys = |value: Value| {
+            let mut keys: Vec<_> = value.as_object().unwrap().keys().cloned().collect();
+            keys.sort();
+            keys
+        };
+        assert_eq!(
+            keys(checkin.to_webhook_value()),
+            vec![
+                "checked_in_at",
+                "is_private",
+                "is_too_sensitive",
+                "link",
+                "note",
+                "source",
+                "tags"
+            ]
+        );
+        assert_eq!(
+            keys(checkin.to_api_value()),
+            vec![
+                "checked_in_at",
+                "is_private",
+                "is_too_sensitive",
+                "link",
+                "note",
+                "tags"
+            ]
+        );
+        assert_eq!(
+            keys(Checkin::builder().build().to_webhook_value()),
+            vec!["checked_in_at", "tags"]
+        );
+    }
+
+    #[test]
+    fn tags_survive_build() {
+        let mut builder = Checkin::builder();
+        builder.tags([" anime ", "", "fav", "  "]).unwrap();
+        let checkin = builder.build();
+        assert_eq!(checkin.tags().collect::<Vec<_>>(), vec!["anime", "fav"]);
+    }
+
+    #[test]
+    fn note_length_boundary() {
+        let mut builder = Checkin::builder();
+        assert!(builder.note(&"a".repeat(NOTE_MAX_CHARS)).is_ok());
+        assert!(builder.note(&"あ".repeat(NOTE_MAX_CHARS)).is_ok());
+        assert_eq!(
+            builder.note(&"a".repeat(NOTE_MAX_CHARS + 1)).unwrap_err(),
+            CheckinError::TooLong {
+                field: "note",
+                limit: NOTE_MAX_CHARS,
+                actual: NOTE_MAX_CHARS + 1,
+            }
+        );
+    }
+
+    #[test]
+    fn link_length_boundary() {
+        let prefix = "https://example.com/";
+        let mut builder = Checkin::builder();
+        let link = format!("{}{}", prefix, "a".repeat(LINK_MAX_CHARS - prefix.len()));
+        assert!(builder.link(&link).is_ok());
+        let link = format!(
+            "{}{}",
+            prefix,
+            "a".repeat(LINK_MAX_CHARS - prefix.len() + 1)
+        );
+        assert_eq!(
+            builder.link(&link).unwrap_err(),
+            CheckinError::TooLong {
+                field: "link",
+                limit: LINK_MAX_CHARS,
+                actual: LINK_MAX_CHARS + 1,
+            }
+        );
+    }
+
+    #[test]
+    fn add_tag_preserves_order() {
+        let mut builder = Checkin::builder();
+        builder.tags(["first"]).unwrap();
+        builder.add_tag(" second ").unwrap();
+        builder.add_tag("").unwrap();
+        builder.add_tag("third").unwrap();
+        builder.add_tag("fourth").unwrap();
+        assert_eq!(
+            builder.add_tag("a b").unwrap_err(),
+            CheckinError::HasWhitespaces
+        );
+
+        let checkin = builder.build();
+        assert_eq!(
+            checkin.tags().collect::<Vec<_>>(),
+            vec!["first", "second", "third", "fourth"]
+        );
+    }
+
+    #[test]
+    fn source_round_trips() {
+        let mut builder = Checkin::builder();
+        builder.source("my-app/1.2").unwrap();
+        let checkin = builder.build();
+        assert_eq!(checkin.source().map(String::as_str), Some("my-app/1.2"));
+
+        let value = serde_json::to_value(&checkin).unwrap();
+        assert_eq!(value["source"], "my-app/1.2");
+        let deserialized: Checkin = serde_json::from_value(value).unwrap();
+        assert_eq!(deserialized, checkin);
+
+        let mut builder = Checkin::builder();
+        assert!(matches!(
+            builder.source(&"a".repeat(SOURCE_MAX_CHARS + 1)),
+            Err(CheckinError::TooLong {
+                field: "source",
+                ..
+            })
+        ));
+    }
+
+    fn received() -> ReceivedCheckin {
+        serde_json::from_value(serde_json::json!({
+            "id": 1,
+            "checked_in_at": "2021-01-02T03:04:05+09:00",
+            "note": "note",
+            "link": "",
+            "tags": ["anime", "fav"],
+            "source": "web",
+            "is_private": true,
+            "is_too_sensitive": false,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn builder_from_received() {
+        let received = received();
+        let builder = CheckinBuilder::<Local>::from_received(&received);
+        assert_eq!(builder.checked_in_at(), received.checked_in_at());
+
+        let checkin = builder.build();
+        assert_eq!(checkin.note().map(String::as_str), Some("note"));
+        assert_eq!(checkin.link(), None);
+        assert_eq!(checkin.tags().collect::<Vec<_>>(), vec!["anime", "fav"]);
+        assert_eq!(checkin.is_private(), Some(true));
+        assert_eq!(checkin.is_too_sensitive(), Some(false));
+        assert_eq!(checkin.source(), None);
+    }
+
+    #[test]
+    fn clearing_fields() {
+        let mut builder = Checkin::builder();
+        builder
+            .note("note")
+            .unwrap()
+            .link("https://example.com")
+            .unwrap()
+            .tags(["anime"])
+            .unwrap();
+        builder.clear_note().clear_link().clear_tags();
+
+        let checkin = builder.build();
+        assert_eq!(checkin.note(), None);
+        assert_eq!(checkin.link(), None);
+        assert_eq!(checkin.tags().count(), 0);
+    }
+
+    #[test]
+    fn default_is_new_local() {
+        let default = CheckinBuilder::<Local>::default();
+        let new_local = CheckinBuilder::<Local>::new_local();
+        let elapsed = new_local
+            .checked_in_at()
+            .signed_duration_since(*default.checked_in_at());
+        assert!(elapsed >= Duration::zero() && elapsed < Duration::seconds(1));
+
+        let mut default = default.build().to_webhook_value();
+        let mut new_local = new_local.build().to_webhook_value();
+        default["checked_in_at"].take();
+        new_local["checked_in_at"].take();
+        assert_eq!(default, new_local);
+    }
+
+    #[test]
+    fn checked_in_at_reads_back() {
+        let datetime = Utc.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap();
+        let builder = CheckinBuilder::with_datetime(datetime);
+        assert_eq!(*builder.checked_in_at(), datetime);
+    }
+
+    #[test]
+    fn seconds_format_millis() {
+        let datetime = Utc.timestamp_millis_opt(1_700_000_000_123).unwrap();
+        let mut builder = CheckinBuilder::with_datetime(datetime);
+        builder.seconds_format(SecondsFormat::Millis);
+        assert_eq!(builder.build().checked_in_at(), "2023-11-14T22:13:20.123Z");
+
+        let builder = CheckinBuilder::with_datetime(datetime);
+        assert_eq!(builder.build().checked_in_at(), "2023-11-14T22:13:20Z");
+    }
+
+    #[test]
+    fn unix_timestamps() {
+        let checkin = CheckinBuilder::<Utc>::from_unix_timestamp(0)
+            .unwrap()
+            .build();
+        assert_eq!(checkin.checked_in_at(), "1970-01-01T00:00:00Z");
+        let checkin = CheckinBuilder::<Utc>::from_unix_timestamp(1_700_000_000)
+            .unwrap()
+            .build();
+        assert_eq!(checkin.checked_in_at(), "2023-11-14T22:13:20Z");
+        assert_eq!(
+            CheckinBuilder::<Utc>::from_unix_timestamp(i64::MAX).unwrap_err(),
+            CheckinError::OutOfRange
+        );
+    }
+
+    #[test]
+    fn try_build_reports_all_errors() {
+        let future = Utc::now() + Duration::days(1);
+        let mut builder = CheckinBuilder::with_datetime(future);
+        builder.note("abcdef").unwrap();
+        builder.note_limit(3);
+
+        let errors = builder.try_build().unwrap_err();
+        assert_eq!(
+            errors,
+            vec![
+                CheckinError::TooLong {
+                    field: "note",
+                    limit: 3,
+                    actual: 6,
+                },
+                CheckinError::FutureTimestamp,
+            ]
+        );
+    }
+
+    #[test]
+    fn too_long_has_details() {
+        let mut builder = Checkin::builder();
+        let error = builder.note(&"a".repeat(612)).unwrap_err();
+        assert_eq!(
+            error,
+            CheckinError::TooLong {
+                field: "note",
+                limit: NOTE_MAX_CHARS,
+                actual: 612,
+            }
+        );
+        assert_eq!(error.to_string(), "note was 612 chars, limit is 500");
+    }
+
+    #[test]
+    fn dedup_tags() {
+        let mut builder = Checkin::builder();
+        builder.tags(["anime", "Anime", "anime", "fav"]).unwrap();
+        builder.dedup_tags();
+        assert_eq!(builder.clone().build().tags().count(), 3);
+
+        builder.dedup_tags_ignore_case();
+        let checkin = builder.build();
+        assert_eq!(checkin.tags().collect::<Vec<_>>(), vec!["anime", "fav"]);
+    }
+
+    #[test]
+    fn tag_count_boundary() {
+        let tags: Vec<_> = (0..MAX_TAGS).map(|i| format!("tag{}", i)).collect();
+        let mut builder = Checkin::builder();
+        builder.tags(&tags).unwrap();
+        assert_eq!(
+            builder.add_tag("over").unwrap_err(),
+            CheckinError::TooManyTags
+        );
+
+        let mut tags = tags;
+        tags.push("over".into());
+        let mut builder = Checkin::builder();
+        assert_eq!(builder.tags(&tags).unwrap_err(), CheckinError::TooManyTags);
+    }
+
+    #[test]
+    fn link_url_validation() {
+        let mut builder = Checkin::builder();
+        assert!(builder.link("https://example.com/cyan.png").is_ok());
+        #[cfg(feature = "url")]
+        {
+            assert_eq!(
+                builder.link("example.com").unwrap_err(),
+                CheckinError::InvalidUrl
+            );
+            assert_eq!(builder.link("").unwrap_err(), CheckinError::InvalidUrl);
+            assert_eq!(
+                builder.link("ftp://example.com").unwrap_err(),
+                CheckinError::InvalidUrl
+            );
+        }
+        #[cfg(not(feature = "url"))]
+        {
+            assert!(builder.link("example.com").is_ok());
+            assert!(builder.link("").is_ok());
+        }
+    }
+
+    fn full_checkin() -> Checkin {
+        let mut builder = CheckinBuilder::<Utc>::from_unix_timestamp(0).unwrap();
+        builder
+            .note("note")
+            .unwrap()
+            .link("https://example.com")
+            .unwrap()
+            .source("app")
+            .unwrap()
+            .tags(["anime", "fav"])
+            .unwrap()
+            .is_private(true)
+            .is_too_sensitive(false);
+        builder.build()
+    }
+
+    #[test]
+    fn checkin_round_trips() {
+        let checkin = full_checkin();
+        let json = serde_json::to_string(&checkin).unwrap();
+        let deserialized: Checkin = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, checkin);
+
+        let minimal: Checkin =
+            serde_json::from_str(r#"{ "checked_in_at": "1970-01-01T00:00:00Z" }"#).unwrap();
+        assert_eq!(minimal.note(), None);
+        assert_eq!(minimal.tags().count(), 0);
+    }
+
+    #[test]
+    fn minimal_checkin_omits_unset_fields() {
+        let checkin = CheckinBuilder::<Utc>::from_unix_timestamp(0)
+            .unwrap()
+            .build();
+        assert_eq!(
+            serde_json::to_value(&checkin).unwrap(),
+            serde_json::json!({ "checked_in_at": "1970-01-01T00:00:00Z", "tags": [] })
+        );
+    }
+
+    #[test]
+    fn clone_is_equal() {
+        let checkin = full_checkin();
+        assert_eq!(checkin.clone(), checkin);
+    }
+
+    #[test]
+    fn checkin_from_received() {
+        let received = received();
+        let checkin = Checkin::from(&received);
+        let value = checkin.to_webhook_value();
+
+        let checked_in_at = DateTime::parse_from_rfc3339(value["checked_in_at"].as_str().unwrap());
+        assert_eq!(checked_in_at.unwrap(), *received.checked_in_at());
+        assert_eq!(value["note"], "note");
+        assert_eq!(value["tags"], serde_json::json!(["anime", "fav"]));
+        assert_eq!(value["is_private"], true);
+        assert_eq!(value["is_too_sensitive"], false);
+        assert!(value.get("link").is_none());
+        assert!(value.get("source").is_none());
+    }
+
+    #[test]
+    fn tags_from_delimited_string() {
+        let mut builder = CheckinBuilder::<Utc>::from_unix_timestamp(0).unwrap();
+        builder.tags_from_str("anime, fav,, cyan ,", ',').unwrap();
+        let checkin = builder.build();
+        assert_eq!(checkin.tags().collect::<Vec<_>>(), ["anime", "fav", "cyan"]);
+
+        let mut builder = CheckinBuilder::<Utc>::from_unix_timestamp(0).unwrap();
+        builder.tags_from_str("  anime fav   cyan ", ' ').unwrap();
+        let checkin = builder.build();
+        assert_eq!(checkin.tags().collect::<Vec<_>>(), ["anime", "fav", "cyan"]);
+
+        let mut builder = CheckinBuilder::<Utc>::from_unix_timestamp(0).unwrap();
+        assert!(matches!(
+            builder.tags_from_str("anime, f av", ','),
+            Err(CheckinError::HasWhitespaces)
+        ));
+    }
+
+    #[cfg(feature = "unicode-normalization")]
+    #[test]
+    fn note_and_tags_are_normalized_into_nfc() {
+        let decomposed = "cafe\u{301}";
+        assert_eq!(decomposed.chars().count(), 5);
+
+        let mut builder = CheckinBuilder::<Utc>::from_unix_timestamp(0).unwrap();
+        builder.note(decomposed).unwrap();
+        builder.tags([decomposed]).unwrap();
+        let checkin = builder.build();
+        let note = checkin.note().unwrap();
+        assert_eq!(note, "caf\u{e9}");
+        assert_eq!(note.chars().count(), 4);
+        assert_eq!(checkin.tags().collect::<Vec<_>>(), ["caf\u{e9}"]);
+
+        let mut builder = CheckinBuilder::<Utc>::from_unix_timestamp(0).unwrap();
+        builder.normalize_nfc(false).note(decomposed).unwrap();
+        assert_eq!(builder.build().note().unwrap(), decomposed);
+    }
+
+    #[test]
+    fn naive_local_keeps_offset() {
+        let naive = NaiveDate::from_ymd_opt(2024, 1, 2)
+            .unwrap()
+            .and_hms_opt(3, 4, 5)
+            .unwrap();
+        let jst = FixedOffset::east_opt(9 * 3600).unwrap();
+        let checkin = CheckinBuilder::with_naive_local(naive, jst)
+            .unwrap()
+            .build();
+        assert_eq!(checkin.checked_in_at(), "2024-01-02T03:04:05+09:00");
+
+        let pst = FixedOffset::west_opt(8 * 3600).unwrap();
+        let checkin = CheckinBuilder::with_naive_local(naive, pst)
+            .unwrap()
+            .build();
+        assert_eq!(checkin.checked_in_at(), "2024-01-02T03:04:05-08:00");
+    }
+
+    #[test]
+    fn builder_from_rfc3339() {
+        let builder = CheckinBuilder::with_rfc3339("2024-01-02T03:04:05+09:00").unwrap();
+        assert_eq!(builder.checked_in_at().offset().local_minus_utc(), 9 * 3600);
+        assert_eq!(builder.build().checked_in_at(), "2024-01-02T03:04:05+09:00");
+
+        assert!(matches!(
+            CheckinBuilder::with_rfc3339("2024-01-02 03:04"),
+            Err(CheckinError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn raised_note_limit_allows_longer_note() {
+        let long_note = "a".repeat(NOTE_MAX_CHARS + 100);
+        let mut builder = CheckinBuilder::<Utc>::from_unix_timestamp(0).unwrap();
+        assert!(matches!(
+            builder.note(&long_note),
+            Err(CheckinError::TooLong { .. })
+        ));
+
+        builder.note_limit(NOTE_MAX_CHARS * 2);
+        builder.note(&long_note).unwrap();
+        assert!(builder.validate().is_empty());
+        assert_eq!(builder.build().note(), Some(&long_note));
+    }
+
+    #[test]
+    fn control_characters_in_note() {
+        let mut builder = CheckinBuilder::<Utc>::from_unix_timestamp(0).unwrap();
+        for note in &["null\0byte", "vertical\u{b}tab", "escape\u{1b}[0m"] {
+            assert!(matches!(
+                builder.note(note),
+                Err(CheckinError::InvalidCharacter)
+            ));
+        }
+
+        builder.note("first line\r\nsecond\tline").unwrap();
+        assert_eq!(
+            builder.build().note().map(String::as_str),
+            Some("first line\r\nsecond\tline")
+        );
+    }
+
+    #[cfg(not(feature = "full-debug"))]
+    #[test]
+    fn debug_redacts_note_and_link() {
+        let mut builder = CheckinBuilder::<Utc>::from_unix_timestamp(0).unwrap();
+        builder
+            .note("secret note")
+            .unwrap()
+            .link("https://example.com/secret")
+            .unwrap();
+        let builder_debug = format!("{:?}", builder);
+        let checkin_debug = format!("{:?}", builder.build());
+        for debug in &[builder_debug, checkin_debug] {
+            assert!(!debug.contains("secret"), "{}", debug);
+            assert!(
+                debug.contains("note: Some(<redacted 11 chars>)"),
+                "{}",
+                debug
+            );
+            assert!(
+                debug.contains("link: Some(<redacted 26 chars>)"),
+                "{}",
+                debug
+            );
+        }
+
+        let received_debug = format!("{:?}", received());
+        assert!(received_debug.contains("note: Some(<redacted 4 chars>)"));
+        assert!(received_debug.contains("tags: [\"anime\", \"fav\"]"));
+    }
+
+    #[test]
+    fn tag_warnings_report_trimmed_and_dropped() {
+        let mut builder = CheckinBuilder::<Utc>::from_unix_timestamp(0).unwrap();
+        builder.tags([" a ", "", "b\t"]).unwrap();
+        assert_eq!(
+            builder.last_tag_warnings(),
+            [
+                TagWarning::Altered {
+                    index: 0,
+                    original: " a ".into(),
+                    tag: "a".into(),
+                },
+                TagWarning::Dropped {
+                    index: 1,
+                    original: "".into(),
+                },
+                TagWarning::Altered {
+                    index: 2,
+                    original: "b\t".into(),
+                    tag: "b".into(),
+                },
+            ]
+        );
+        assert_eq!(builder.build().tags().collect::<Vec<_>>(), ["a", "b"]);
+
+        let mut builder = CheckinBuilder::<Utc>::from_unix_timestamp(0).unwrap();
+        builder.tags([" a "]).unwrap().add_tag("b").unwrap();
+        assert!(builder.last_tag_warnings().is_empty());
+    }
+
+    #[test]
+    fn builder_shortcuts() {
+        let checkin = Checkin::builder_utc().build();
+        assert!(checkin.checked_in_at().ends_with('Z'));
+
+        let local = Checkin::builder();
+        let offset = local.checked_in_at().offset().fix();
+        assert_eq!(offset, Local::now().offset().fix());
+    }
+
+    #[test]
+    fn checkin_sends_itself() {
+        use crate::{requester::MockRequester, tissue::IncomingEndpoint};
+        use futures::executor::block_on;
+
+        let requester = MockRequester::with_responses(vec![serde_json::json!({
+            "status": 422,
+            "error": { "violations": ["note is too long"] },
+        })]);
+        let endpoint = IncomingEndpoint::new("id", requester.clone());
+        let checkin = with_note("Hello");
+
+        let response = block_on(checkin.send(&endpoint)).unwrap();
+        assert!(!response.is_success());
+        let requests = requester.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(
+            requests[0].body(),
+            Some(&endpoint.checkin_body(&checkin).unwrap())
+        );
+    }
+
+    #[test]
+    fn offset_hours_range() {
+        let checkin = CheckinBuilder::with_offset_hours(9).unwrap().build();
+        assert!(checkin.checked_in_at().ends_with("+09:00"));
+        let checkin = CheckinBuilder::with_offset_hours(-12).unwrap().build();
+        assert!(checkin.checked_in_at().ends_with("-12:00"));
+        assert!(CheckinBuilder::with_offset_hours(14).is_ok());
+
+        for hours in &[-13, 15, i32::MAX] {
+            assert!(matches!(
+                CheckinBuilder::with_offset_hours(*hours),
+                Err(CheckinError::OutOfRange)
+            ));
+        }
+    }
+
+    #[test]
+    fn round_to_minute_floors_seconds() {
+        let mut builder = CheckinBuilder::with_rfc3339("2024-01-02T03:04:59.999+09:00").unwrap();
+        builder
+            .round_to_minute()
+            .seconds_format(SecondsFormat::Millis);
+        assert_eq!(
+            builder.build().checked_in_at(),
+            "2024-01-02T03:04:00.000+09:00"
+        );
+
+        let mut builder = CheckinBuilder::with_rfc3339("2024-01-02T03:04:00+09:00").unwrap();
+        builder.round_to_minute();
+        assert_eq!(builder.build().checked_in_at(), "2024-01-02T03:04:00+09:00");
     }
 }
@@ -2,26 +2,366 @@
 
 use std::{
     error::Error,
-    fmt::{Display, Formatter, Result as FmtResult},
+    fmt::{Debug, Display, Formatter, Result as FmtResult},
+    time::Duration,
 };
 
+use serde_json::Value;
+
 /// Describes an error on checkins.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
 pub enum CheckinError {
     /// Too long parameters
-    TooLong,
+    TooLong {
+        /// Name of the field
+        field: &'static str,
+
+        /// Maximum length in characters
+        limit: usize,
+
+        /// Actual length in characters
+        actual: usize,
+    },
 
     /// Some tag have whitespaces
     HasWhitespaces,
+
+    /// Too many tags
+    TooManyTags,
+
+    /// Some tag starts with `#`
+    InvalidTag,
+
+    /// Link is not a valid URL
+    InvalidUrl,
+
+    /// Note has a disallowed control character
+    InvalidCharacter,
+
+    /// Value out of range
+    OutOfRange,
+
+    /// Timestamp too far in the future
+    FutureTimestamp,
+
+    /// Timestamp could not be parsed
+    Parse(chrono::ParseError),
 }
 
 impl Display for CheckinError {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
         match self {
-            CheckinError::TooLong => write!(f, "The parameter was too long"),
+            CheckinError::TooLong {
+                field,
+                limit,
+                actual,
+            } => write!(f, "{} was {} chars, limit is {}", field, actual, limit),
             CheckinError::HasWhitespaces => write!(f, "The parameter had whitespaces"),
+            CheckinError::TooManyTags => write!(f, "There were too many tags"),
+            CheckinError::InvalidTag => write!(f, "The tag started with #"),
+            CheckinError::InvalidUrl => write!(f, "The link was not a valid URL"),
+            CheckinError::InvalidCharacter => write!(f, "The note had a control character"),
+            CheckinError::OutOfRange => write!(f, "The parameter was out of range"),
+            CheckinError::FutureTimestamp => write!(f, "The timestamp was too far in the future"),
+            CheckinError::Parse(error) => write!(f, "The timestamp could not be parsed: {}", error),
+        }
+    }
+}
+
+impl Error for CheckinError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            CheckinError::Parse(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// Describes an error on requests to Tissue.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum TissueError {
+    /// The request did not complete in time
+    Timeout(Duration),
+
+    /// Authentication failed or was not permitted, with HTTP status code
+    Unauthorized(u16),
+
+    /// The resource was not found
+    NotFound,
+
+    /// The response did not have expected structure
+    MalformedResponse(Value),
+
+    /// The response had a status code not handled
+    UnexpectedStatus {
+        /// Status code
+        status: u64,
+
+        /// Response body
+        body: Value,
+    },
+
+    /// The URL was not valid for the request
+    InvalidUrl(String),
+
+    /// The webhook ID had characters not allowed in the URL path
+    InvalidId(String),
+
+    /// The requester failed to send the request or receive the response
+    Transport {
+        /// Attempted URL, with the webhook ID hidden for `IncomingEndpoint`
+        url: String,
+
+        /// Error from the requester
+        source: Box<dyn Error + Send + Sync + 'static>,
+    },
+
+    /// JSON could not be serialized or deserialized
+    Serialization(serde_json::Error),
+
+    /// The checkin was not valid
+    Validation(CheckinError),
+}
+
+impl Display for TissueError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            TissueError::Timeout(timeout) => {
+                write!(f, "The request timed out after {:?}", timeout)
+            }
+            TissueError::Unauthorized(status) => {
+                write!(f, "The request was unauthorized with status {}", status)
+            }
+            TissueError::NotFound => write!(f, "The resource was not found"),
+            TissueError::MalformedResponse(value) => {
+                write!(f, "The response was malformed: {}", value)
+            }
+            TissueError::UnexpectedStatus { status, body } => {
+                write!(f, "Unknown status code: {}, response: {}", status, body)
+            }
+            TissueError::InvalidUrl(url) => write!(f, "The URL was invalid: {}", url),
+            TissueError::InvalidId(id) => write!(f, "The webhook ID was invalid: {}", id),
+            TissueError::Transport { url, source } => {
+                write!(f, "The request to {} failed: {}", url, source)
+            }
+            TissueError::Serialization(error) => write!(f, "JSON conversion failed: {}", error),
+            TissueError::Validation(error) => write!(f, "The checkin was invalid: {}", error),
         }
     }
 }
 
-impl Error for CheckinError {}
+impl TissueError {
+    /// Name of the variant, for diagnostics without the response body.
+    #[cfg(feature = "log")]
+    pub(crate) fn kind(&self) -> &'static str {
+        match self {
+            TissueError::Timeout(_) => "Timeout",
+            TissueError::Unauthorized(_) => "Unauthorized",
+            TissueError::NotFound => "NotFound",
+            TissueError::MalformedResponse(_) => "MalformedResponse",
+            TissueError::UnexpectedStatus { .. } => "UnexpectedStatus",
+            TissueError::InvalidUrl(_) => "InvalidUrl",
+            TissueError::InvalidId(_) => "InvalidId",
+            TissueError::Transport { .. } => "Transport",
+            TissueError::Serialization(_) => "Serialization",
+            TissueError::Validation(_) => "Validation",
+        }
+    }
+}
+
+impl From<serde_json::Error> for TissueError {
+    fn from(error: serde_json::Error) -> TissueError {
+        TissueError::Serialization(error)
+    }
+}
+
+impl From<CheckinError> for TissueError {
+    fn from(error: CheckinError) -> TissueError {
+        TissueError::Validation(error)
+    }
+}
+
+impl Error for TissueError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            TissueError::Transport { source, .. } => Some(source.as_ref()),
+            TissueError::Serialization(error) => Some(error),
+            TissueError::Validation(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// Requester error with a secret like the webhook ID masked in its message.
+/// `source` returns the original error, so that it can still be downcast;
+/// the messages of the original error and its sources are not masked.
+pub(crate) struct RedactedError {
+    inner: Box<dyn Error + Send + Sync + 'static>,
+    secret: String,
+    mask: String,
+}
+
+impl RedactedError {
+    /// Wraps `error` if its message has `secret`, to show `mask` instead of it.
+    /// The error is kept as is otherwise.
+    pub(crate) fn wrap(
+        error: Box<dyn Error + Send + Sync + 'static>,
+        secret: String,
+        mask: String,
+    ) -> Box<dyn Error + Send + Sync + 'static> {
+        if secret.is_empty() {
+            return error;
+        }
+        let redacted = RedactedError {
+            inner: error,
+            secret,
+            mask,
+        };
+        if redacted.masked() == redacted.inner.to_string() {
+            redacted.inner
+        } else {
+            Box::new(redacted)
+        }
+    }
+
+    /// Message of the original error with `secret` masked,
+    /// except where it is followed by more characters of an ID or path segment.
+    fn masked(&self) -> String {
+        let message = self.inner.to_string();
+        let continues = |c: char| c.is_ascii_alphanumeric() || matches!(c, '-' | '_');
+        let mut masked = String::with_capacity(message.len());
+        let mut rest = message.as_str();
+        while let Some(position) = rest.find(&self.secret) {
+            let end = position + self.secret.len();
+            masked.push_str(&rest[..position]);
+            if rest[end..].starts_with(continues) {
+                masked.push_str(&self.secret);
+            } else {
+                masked.push_str(&self.mask);
+            }
+            rest = &rest[end..];
+        }
+        masked.push_str(rest);
+        masked
+    }
+}
+
+impl Debug for RedactedError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_tuple("RedactedError")
+            .field(&self.masked())
+            .finish()
+    }
+}
+
+impl Display for RedactedError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str(&self.masked())
+    }
+}
+
+impl Error for RedactedError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(self.inner.as_ref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chrono::DateTime;
+
+    #[test]
+    fn source_reaches_wrapped_error() {
+        let error = TissueError::Transport {
+            url: "https://shikorism.net/api/webhooks/checkin/***".into(),
+            source: "connection reset".into(),
+        };
+        assert_eq!(error.source().unwrap().to_string(), "connection reset");
+
+        let parse_error = DateTime::parse_from_rfc3339("garbage").unwrap_err();
+        let error = TissueError::Validation(CheckinError::Parse(parse_error));
+        let checkin_error = error.source().unwrap();
+        assert_eq!(
+            checkin_error.to_string(),
+            CheckinError::Parse(parse_error).to_string()
+        );
+        assert_eq!(
+            checkin_error.source().unwrap().to_string(),
+            parse_error.to_string()
+        );
+
+        assert!(TissueError::NotFound.source().is_none());
+    }
+
+    #[test]
+    fn converts_into_tissue_error() {
+        let serde_error = serde_json::from_str::<Value>("{").unwrap_err();
+        assert!(matches!(
+            TissueError::from(serde_error),
+            TissueError::Serialization(_)
+        ));
+
+        fn parse(json: &str) -> Result<Value, TissueError> {
+            Ok(serde_json::from_str(json)?)
+        }
+        assert!(matches!(parse("{"), Err(TissueError::Serialization(_))));
+
+        assert!(matches!(
+            TissueError::from(CheckinError::TooManyTags),
+            TissueError::Validation(CheckinError::TooManyTags)
+        ));
+    }
+
+    #[test]
+    fn garbage_timestamp_is_parse_error() {
+        let error = match crate::CheckinBuilder::with_rfc3339("not a timestamp") {
+            Err(error) => error,
+            Ok(_) => panic!("Garbage should not be parsed"),
+        };
+        let parse_error = match &error {
+            CheckinError::Parse(parse_error) => parse_error,
+            error => panic!("Unexpected error: {:?}", error),
+        };
+        assert_eq!(
+            error.to_string(),
+            format!("The timestamp could not be parsed: {}", parse_error)
+        );
+        assert_eq!(error.source().unwrap().to_string(), parse_error.to_string());
+    }
+
+    #[test]
+    fn redacted_error_keeps_source() {
+        #[derive(Debug)]
+        struct Inner;
+
+        impl Display for Inner {
+            fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+                write!(f, "error sending to https://example.com/checkin/ab: ab bad")
+            }
+        }
+
+        impl Error for Inner {}
+
+        let error =
+            RedactedError::wrap(Box::new(Inner), "/checkin/ab".into(), "/checkin/***".into());
+        assert_eq!(
+            error.to_string(),
+            "error sending to https://example.com/checkin/***: ab bad"
+        );
+        assert!(!format!("{:?}", error).contains("/checkin/ab"));
+        assert!(error.source().unwrap().downcast_ref::<Inner>().is_some());
+
+        // A longer ID sharing the prefix is left as is
+        let error = RedactedError::wrap(
+            "failed at /checkin/abc".into(),
+            "/checkin/ab".into(),
+            "/checkin/***".into(),
+        );
+        assert_eq!(error.to_string(), "failed at /checkin/abc");
+        assert!(error.source().is_none());
+    }
+}
@@ -1,32 +1,167 @@
+//! Client for Tissue, through Incoming Webhook or the authenticated API.
+//!
+//! Enums marked `#[non_exhaustive]`, such as `CheckinResponse` and `TissueError`,
+//! may get more variants without a major version, so matches on them need a wildcard arm.
+
+mod api;
+#[cfg(feature = "blocking")]
+mod blocking;
 mod checkin;
 mod error;
+mod multi;
+mod query;
+mod queue;
+mod requester;
 mod tissue;
 
 pub use crate::{
-    checkin::{Checkin, CheckinBuilder},
-    error::CheckinError,
-    tissue::{CheckinResponse, IncomingEndpoint, ReceivedCheckin},
+    api::{CheckinFilter, Page, Stats, TagSuggestion, TissueClient, User},
+    checkin::{
+        Checkin, CheckinBuilder, Clock, SystemClock, TagWarning, LINK_MAX_CHARS, MAX_TAGS,
+        NOTE_MAX_CHARS, SOURCE_MAX_CHARS,
+    },
+    error::{CheckinError, TissueError},
+    multi::MultiEndpoint,
+    queue::CheckinQueue,
+    tissue::{
+        BoxedIncomingEndpoint, ById, CheckinResponse, IncomingEndpoint, ReceivedCheckin,
+        ReceivedCheckinRef, SourceKind, Violation,
+    },
 };
 
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+pub use crate::requester::FetchRequester;
+#[cfg(feature = "reqwest")]
+pub use crate::requester::ReqwestRequester;
+#[cfg(feature = "surf")]
+pub use crate::requester::SurfRequester;
+#[cfg(any(test, feature = "test-util"))]
+pub use crate::requester::{MockRequest, MockRequester};
+
 use async_trait::async_trait;
 use serde_json::Value;
-use std::{collections::HashMap, error::Error};
+use std::{collections::HashMap, error::Error, sync::Arc};
 
 /// Trait that processes requests for Tissue.
+/// Methods take `&self`, so one requester can be shared by concurrent requests.
+/// Given headers replace the default ones of the requester, such as `Content-Type`.
+///
+/// Each method returns the JSON body of the response even for non-2xx status.
+/// If the body is an object without `status`, the HTTP status code should be inserted
+/// as `status`, and `Retry-After` header should be inserted as `retry_after` in seconds.
+/// A body other than a JSON object should be returned in an object as `body`, with `status`,
+/// so that the status is not lost. Only failures to get a response should be returned as `Err`.
 #[async_trait]
-pub trait TissueRequester {
+pub trait TissueRequester: Send + Sync {
     /// Does a GET request.
     async fn get(
-        &mut self,
+        &self,
         url: String,
         headers: HashMap<String, String>,
     ) -> Result<Value, Box<dyn Error + Send + Sync>>;
 
     /// Does a POST request.
     async fn post(
-        &mut self,
+        &self,
         url: String,
         headers: HashMap<String, String>,
         body: Value,
     ) -> Result<Value, Box<dyn Error + Send + Sync>>;
+
+    /// Does a PUT request.
+    /// The default implementation always fails; override it to support
+    /// `TissueClient::update_checkin`.
+    async fn put(
+        &self,
+        url: String,
+        _headers: HashMap<String, String>,
+        _body: Value,
+    ) -> Result<Value, Box<dyn Error + Send + Sync>> {
+        Err(format!("PUT is not supported by this requester: {}", url).into())
+    }
+
+    /// Does a DELETE request.
+    /// The default implementation always fails; override it to support
+    /// `TissueClient::delete_checkin`.
+    async fn delete(
+        &self,
+        url: String,
+        _headers: HashMap<String, String>,
+    ) -> Result<Value, Box<dyn Error + Send + Sync>> {
+        Err(format!("DELETE is not supported by this requester: {}", url).into())
+    }
+}
+
+#[async_trait]
+impl<T: TissueRequester + ?Sized> TissueRequester for Box<T> {
+    async fn get(
+        &self,
+        url: String,
+        headers: HashMap<String, String>,
+    ) -> Result<Value, Box<dyn Error + Send + Sync>> {
+        (**self).get(url, headers).await
+    }
+
+    async fn post(
+        &self,
+        url: String,
+        headers: HashMap<String, String>,
+        body: Value,
+    ) -> Result<Value, Box<dyn Error + Send + Sync>> {
+        (**self).post(url, headers, body).await
+    }
+
+    async fn put(
+        &self,
+        url: String,
+        headers: HashMap<String, String>,
+        body: Value,
+    ) -> Result<Value, Box<dyn Error + Send + Sync>> {
+        (**self).put(url, headers, body).await
+    }
+
+    async fn delete(
+        &self,
+        url: String,
+        headers: HashMap<String, String>,
+    ) -> Result<Value, Box<dyn Error + Send + Sync>> {
+        (**self).delete(url, headers).await
+    }
+}
+
+#[async_trait]
+impl<T: TissueRequester + ?Sized> TissueRequester for Arc<T> {
+    async fn get(
+        &self,
+        url: String,
+        headers: HashMap<String, String>,
+    ) -> Result<Value, Box<dyn Error + Send + Sync>> {
+        (**self).get(url, headers).await
+    }
+
+    async fn post(
+        &self,
+        url: String,
+        headers: HashMap<String, String>,
+        body: Value,
+    ) -> Result<Value, Box<dyn Error + Send + Sync>> {
+        (**self).post(url, headers, body).await
+    }
+
+    async fn put(
+        &self,
+        url: String,
+        headers: HashMap<String, String>,
+        body: Value,
+    ) -> Result<Value, Box<dyn Error + Send + Sync>> {
+        (**self).put(url, headers, body).await
+    }
+
+    async fn delete(
+        &self,
+        url: String,
+        headers: HashMap<String, String>,
+    ) -> Result<Value, Box<dyn Error + Send + Sync>> {
+        (**self).delete(url, headers).await
+    }
 }
@@ -0,0 +1,124 @@
+//! Contains the endpoint sending to multiple webhooks.
+
+use crate::{
+    checkin::Checkin,
+    error::TissueError,
+    tissue::{CheckinResponse, IncomingEndpoint},
+    TissueRequester,
+};
+use std::sync::Arc;
+
+use futures::future::join_all;
+
+/// Represents endpoints for multiple Incoming Webhooks sharing one requester,
+/// e.g. for cross-posting the same checkin.
+pub struct MultiEndpoint<T> {
+    requester: Arc<T>,
+    endpoints: Vec<IncomingEndpoint<Arc<T>>>,
+}
+
+impl<T: TissueRequester> MultiEndpoint<T> {
+    /// Creates a new endpoint without targets.
+    pub fn new(requester: T) -> MultiEndpoint<T> {
+        MultiEndpoint {
+            requester: Arc::new(requester),
+            endpoints: vec![],
+        }
+    }
+
+    /// Adds a target webhook with domain and ID.
+    /// Returns `Err(TissueError::InvalidId)` for an invalid ID as `IncomingEndpoint::try_new` does.
+    pub fn with_target(mut self, domain: &str, id: &str) -> Result<MultiEndpoint<T>, TissueError> {
+        let endpoint = IncomingEndpoint::try_with_domain(domain, id, Arc::clone(&self.requester))?;
+        self.endpoints.push(endpoint);
+        Ok(self)
+    }
+
+    /// Endpoints of the targets, in the order added.
+    pub fn endpoints(&self) -> &[IncomingEndpoint<Arc<T>>] {
+        &self.endpoints
+    }
+
+    /// Sends a checkin to all targets concurrently.
+    /// Results are paired with their endpoints, in the order added.
+    pub async fn send_checkin(
+        &self,
+        checkin: &Checkin,
+    ) -> Vec<(
+        &IncomingEndpoint<Arc<T>>,
+        Result<CheckinResponse, TissueError>,
+    )> {
+        let results = join_all(
+            self.endpoints
+                .iter()
+                .map(|endpoint| endpoint.send_checkin(checkin)),
+        )
+        .await;
+        self.endpoints.iter().zip(results).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::requester::{success_response, MockRequester};
+
+    use futures::executor::block_on;
+    use serde_json::json;
+
+    #[test]
+    fn target_id_is_validated() {
+        let multi = MultiEndpoint::new(MockRequester::new())
+            .with_target("shikorism.net", "first")
+            .unwrap()
+            .with_target("tissue.example.com", "second")
+            .unwrap();
+        let ids: Vec<_> = multi
+            .endpoints()
+            .iter()
+            .map(|endpoint| endpoint.id())
+            .collect();
+        assert_eq!(ids, vec!["first", "second"]);
+
+        assert!(matches!(
+            MultiEndpoint::new(MockRequester::new()).with_target("x", "a/b?c"),
+            Err(TissueError::InvalidId(_))
+        ));
+    }
+
+    #[test]
+    fn fans_out_to_targets() {
+        let requester = MockRequester::with_responses(vec![
+            success_response(),
+            json!({ "status": 422, "error": { "violations": ["note is too long"] } }),
+        ]);
+        let multi = MultiEndpoint::new(requester.clone())
+            .with_target("shikorism.net", "personal")
+            .unwrap()
+            .with_target("tissue.example.com", "group")
+            .unwrap();
+
+        let mut builder = Checkin::builder();
+        builder.note("Hello").unwrap();
+        let results = block_on(multi.send_checkin(&builder.build()));
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].0.id(), "personal");
+        assert!(matches!(results[0].1, Ok(CheckinResponse::Success(_))));
+        assert_eq!(results[1].0.domain(), "tissue.example.com");
+        assert!(matches!(
+            results[1].1,
+            Ok(CheckinResponse::ValidationError(_))
+        ));
+
+        let requests = requester.requests();
+        let urls: Vec<_> = requests.iter().map(|request| request.url()).collect();
+        assert_eq!(
+            urls,
+            vec![
+                "https://shikorism.net/api/webhooks/checkin/personal",
+                "https://tissue.example.com/api/webhooks/checkin/group",
+            ]
+        );
+        assert_eq!(requests[0].body(), requests[1].body());
+    }
+}
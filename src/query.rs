@@ -0,0 +1,18 @@
+//! Contains helpers for URL query strings.
+
+use std::fmt::Write;
+
+/// Percent-encodes a query component, leaving only unreserved characters as is.
+pub(crate) fn percent_encode(text: &str) -> String {
+    let mut encoded = String::with_capacity(text.len());
+    for byte in text.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => write!(encoded, "%{:02X}", byte).expect("Writing to String should not fail"),
+        }
+    }
+
+    encoded
+}
@@ -0,0 +1,149 @@
+//! Contains the queue of checkins waiting to be sent.
+
+use crate::{
+    checkin::Checkin,
+    error::TissueError,
+    tissue::{CheckinResponse, IncomingEndpoint},
+    TissueRequester,
+};
+use std::collections::{vec_deque, VecDeque};
+
+use serde::{Deserialize, Serialize};
+
+/// Queue of checkins to send later, e.g. while offline.
+/// It serializes as an array of checkins, so that it can be persisted.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct CheckinQueue {
+    pending: VecDeque<Checkin>,
+}
+
+impl CheckinQueue {
+    /// Creates an empty queue.
+    pub fn new() -> CheckinQueue {
+        CheckinQueue::default()
+    }
+
+    /// Adds a checkin to the end of the queue.
+    pub fn push(&mut self, checkin: Checkin) {
+        self.pending.push_back(checkin);
+    }
+
+    /// Count of pending checkins.
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Whether no checkins are pending.
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Pending checkins, from the oldest.
+    pub fn iter(&self) -> vec_deque::Iter<'_, Checkin> {
+        self.pending.iter()
+    }
+
+    /// Removes and returns the oldest pending checkin,
+    /// e.g. to discard one that flushing keeps stopping at.
+    pub fn pop(&mut self) -> Option<Checkin> {
+        self.pending.pop_front()
+    }
+
+    /// Sends pending checkins in order, removing each one that succeeded or failed validation.
+    /// Returns the responses of the sent ones, and the error if it stopped at one.
+    ///
+    /// Flushing stops at the first other response, such as `RateLimited`, or at the first error,
+    /// leaving that checkin and the rest queued. Such a response comes last in the responses.
+    /// `OtherError` and `Unauthorized` are kept queued as well, as they are not caused by the
+    /// checkin itself (e.g. a deleted webhook gives 404). They stop every flush until the
+    /// endpoint is fixed, or the checkin is discarded with `pop`.
+    pub async fn flush<T: TissueRequester>(
+        &mut self,
+        endpoint: &IncomingEndpoint<T>,
+    ) -> (Vec<CheckinResponse>, Option<TissueError>) {
+        let mut responses = vec![];
+        while let Some(checkin) = self.pending.front() {
+            let response = match endpoint.send_checkin(checkin).await {
+                Ok(response) => response,
+                Err(error) => return (responses, Some(error)),
+            };
+            let removed = matches!(
+                response,
+                CheckinResponse::Success(_) | CheckinResponse::ValidationError(_)
+            );
+            responses.push(response);
+            if !removed {
+                break;
+            }
+            self.pending.pop_front();
+        }
+
+        (responses, None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::requester::{success_response, MockRequester};
+
+    use futures::executor::block_on;
+    use serde_json::json;
+
+    fn checkin(note: &str) -> Checkin {
+        let mut builder = Checkin::builder();
+        builder.note(note).unwrap();
+        builder.build()
+    }
+
+    #[test]
+    fn partial_flush_keeps_the_rest() {
+        let requester = MockRequester::new();
+        requester.push_response(success_response());
+        requester.push_response(json!({
+            "status": 422,
+            "error": { "message": "Validation failed", "violations": ["note is too long"] },
+        }));
+        requester.push_error("connection reset");
+        let endpoint = IncomingEndpoint::new("id", requester);
+
+        let mut queue = CheckinQueue::new();
+        for note in &["first", "second", "third", "fourth"] {
+            queue.push(checkin(note));
+        }
+        let (responses, error) = block_on(queue.flush(&endpoint));
+
+        assert_eq!(responses.len(), 2);
+        assert!(responses[0].is_success());
+        assert!(matches!(responses[1], CheckinResponse::ValidationError(_)));
+        assert!(matches!(error, Some(TissueError::Transport { .. })));
+        let notes: Vec<_> = queue
+            .iter()
+            .map(|checkin| checkin.note().map(String::as_str))
+            .collect();
+        assert_eq!(notes, vec![Some("third"), Some("fourth")]);
+    }
+
+    #[test]
+    fn other_error_stays_queued() {
+        let requester = MockRequester::with_responses(vec![json!({
+            "status": 404,
+            "error": { "message": "Not Found" },
+        })]);
+        let endpoint = IncomingEndpoint::new("id", requester);
+
+        let mut queue = CheckinQueue::new();
+        queue.push(checkin("first"));
+        let (responses, error) = block_on(queue.flush(&endpoint));
+
+        assert!(matches!(
+            responses[..],
+            [CheckinResponse::OtherError { status: 404, .. }]
+        ));
+        assert!(error.is_none());
+        assert_eq!(queue.len(), 1);
+        assert!(queue.pop().is_some());
+        assert!(queue.is_empty());
+    }
+}
@@ -0,0 +1,117 @@
+//! Contains `TissueRequester` implementation with Fetch API.
+
+use super::{decode_body, without_url};
+use crate::TissueRequester;
+use std::{collections::HashMap, error::Error};
+
+use async_trait::async_trait;
+use js_sys::{Promise, Uint8Array};
+use send_wrapper::SendWrapper;
+use serde_json::Value;
+use wasm_bindgen::{prelude::*, JsCast};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{Headers, Request, RequestInit, Response};
+
+#[wasm_bindgen]
+extern "C" {
+    /// Global `fetch`, available in both windows and workers.
+    #[wasm_bindgen(js_name = fetch)]
+    fn fetch_with_request(input: &Request) -> Promise;
+}
+
+/// `TissueRequester` backed by `fetch` of JavaScript.
+/// See `TissueRequester` for the response contract.
+#[derive(Debug, Clone, Default)]
+pub struct FetchRequester {}
+
+impl FetchRequester {
+    /// Creates a new requester.
+    pub fn new() -> FetchRequester {
+        FetchRequester {}
+    }
+
+    async fn send(
+        &self,
+        method: &'static str,
+        url: String,
+        headers: HashMap<String, String>,
+        body: Option<Value>,
+    ) -> Result<Value, Box<dyn Error + Send + Sync>> {
+        // JavaScript values are not `Send`, but wasm32 runs everything on one thread
+        let response = SendWrapper::new(fetch(method, &url, headers, body)).await;
+        let (status, retry_after, body) =
+            response.map_err(|error| without_url(format!("{:?}", error).into(), &url))?;
+
+        Ok(decode_body(status, retry_after.as_deref(), &body))
+    }
+}
+
+/// Does a request and returns status code, `Retry-After` header and body.
+async fn fetch(
+    method: &str,
+    url: &str,
+    headers: HashMap<String, String>,
+    body: Option<Value>,
+) -> Result<(u16, Option<String>, Vec<u8>), JsValue> {
+    let request_headers = Headers::new()?;
+    let init = RequestInit::new();
+    init.set_method(method);
+    if let Some(body) = body {
+        request_headers.set("Content-Type", "application/json")?;
+        init.set_body(&JsValue::from_str(&body.to_string()));
+    }
+    for (key, value) in &headers {
+        request_headers.set(key, value)?;
+    }
+    init.set_headers(&request_headers);
+
+    let request = Request::new_with_str_and_init(url, &init)?;
+    let response: Response = JsFuture::from(fetch_with_request(&request))
+        .await?
+        .dyn_into()?;
+    let retry_after = response.headers().get("Retry-After")?;
+    let buffer = JsFuture::from(response.array_buffer()?).await?;
+
+    Ok((
+        response.status(),
+        retry_after,
+        Uint8Array::new(&buffer).to_vec(),
+    ))
+}
+
+#[async_trait]
+impl TissueRequester for FetchRequester {
+    async fn get(
+        &self,
+        url: String,
+        headers: HashMap<String, String>,
+    ) -> Result<Value, Box<dyn Error + Send + Sync>> {
+        self.send("GET", url, headers, None).await
+    }
+
+    async fn post(
+        &self,
+        url: String,
+        headers: HashMap<String, String>,
+        body: Value,
+    ) -> Result<Value, Box<dyn Error + Send + Sync>> {
+        self.send("POST", url, headers, Some(body)).await
+    }
+
+    async fn put(
+        &self,
+        url: String,
+        headers: HashMap<String, String>,
+        body: Value,
+    ) -> Result<Value, Box<dyn Error + Send + Sync>> {
+        self.send("PUT", url, headers, Some(body)).await
+    }
+
+    async fn delete(
+        &self,
+        url: String,
+        headers: HashMap<String, String>,
+    ) -> Result<Value, Box<dyn Error + Send + Sync>> {
+        self.send("DELETE", url, headers, None).await
+    }
+}
@@ -0,0 +1,198 @@
+//! Contains `TissueRequester` implementation for tests.
+
+use crate::TissueRequester;
+use std::{
+    collections::{HashMap, VecDeque},
+    error::Error,
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+use async_trait::async_trait;
+use serde_json::Value;
+
+/// Request recorded by `MockRequester`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MockRequest {
+    method: &'static str,
+    url: String,
+    headers: HashMap<String, String>,
+    body: Option<Value>,
+}
+
+impl MockRequest {
+    /// HTTP method.
+    pub fn method(&self) -> &str {
+        self.method
+    }
+
+    /// Target URL.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Request headers.
+    pub fn headers(&self) -> &HashMap<String, String> {
+        &self.headers
+    }
+
+    /// Request body.
+    pub fn body(&self) -> Option<&Value> {
+        self.body.as_ref()
+    }
+}
+
+#[derive(Debug, Default)]
+struct MockState {
+    requests: Vec<MockRequest>,
+    responses: VecDeque<Result<Value, String>>,
+}
+
+/// `TissueRequester` that records requests and returns pre-programmed responses in order.
+/// Clones share the same records and responses, so one can be kept for assertions
+/// while another is moved into an endpoint.
+/// When no response is left, requests fail with an error.
+///
+/// # Example
+/// ```
+/// use chrono::prelude::*;
+/// use futures::executor::block_on;
+/// use serde_json::json;
+/// use tissue_rs::{CheckinBuilder, CheckinResponse, IncomingEndpoint, MockRequester};
+///
+/// let requester = MockRequester::new();
+/// requester.push_response(json!({
+///     "status": 422,
+///     "error": { "message": "Validation failed", "violations": ["note is too long"] },
+/// }));
+///
+/// let endpoint = IncomingEndpoint::new("webhook-id", requester.clone());
+/// let mut builder = CheckinBuilder::<Utc>::from_unix_timestamp(0).unwrap();
+/// builder.note("Hello").unwrap();
+/// let response = block_on(endpoint.send_checkin(&builder.build())).unwrap();
+/// assert!(matches!(response, CheckinResponse::ValidationError(_)));
+///
+/// let requests = requester.requests();
+/// assert_eq!(requests[0].method(), "POST");
+/// assert_eq!(
+///     requests[0].url(),
+///     "https://shikorism.net/api/webhooks/checkin/webhook-id"
+/// );
+/// assert_eq!(requests[0].body().unwrap()["note"], "Hello");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MockRequester {
+    state: Arc<Mutex<MockState>>,
+}
+
+impl MockRequester {
+    /// Creates a new requester without any response.
+    pub fn new() -> MockRequester {
+        MockRequester::default()
+    }
+
+    /// Creates a new requester returning `responses` in order.
+    pub fn with_responses<I: IntoIterator<Item = Value>>(responses: I) -> MockRequester {
+        let requester = MockRequester::new();
+        for response in responses {
+            requester.push_response(response);
+        }
+        requester
+    }
+
+    /// Appends a response.
+    pub fn push_response(&self, response: Value) {
+        self.lock().responses.push_back(Ok(response));
+    }
+
+    /// Appends a transport error.
+    pub fn push_error(&self, message: &str) {
+        self.lock().responses.push_back(Err(message.into()));
+    }
+
+    /// Requests recorded so far.
+    pub fn requests(&self) -> Vec<MockRequest> {
+        self.lock().requests.clone()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, MockState> {
+        self.state
+            .lock()
+            .expect("Mock state should not be poisoned")
+    }
+
+    fn respond(
+        &self,
+        method: &'static str,
+        url: String,
+        headers: HashMap<String, String>,
+        body: Option<Value>,
+    ) -> Result<Value, Box<dyn Error + Send + Sync>> {
+        let mut state = self.lock();
+        state.requests.push(MockRequest {
+            method,
+            url,
+            headers,
+            body,
+        });
+
+        match state.responses.pop_front() {
+            Some(response) => response.map_err(Into::into),
+            None => Err("No response is left in MockRequester".into()),
+        }
+    }
+}
+
+#[async_trait]
+impl TissueRequester for MockRequester {
+    async fn get(
+        &self,
+        url: String,
+        headers: HashMap<String, String>,
+    ) -> Result<Value, Box<dyn Error + Send + Sync>> {
+        self.respond("GET", url, headers, None)
+    }
+
+    async fn post(
+        &self,
+        url: String,
+        headers: HashMap<String, String>,
+        body: Value,
+    ) -> Result<Value, Box<dyn Error + Send + Sync>> {
+        self.respond("POST", url, headers, Some(body))
+    }
+
+    async fn put(
+        &self,
+        url: String,
+        headers: HashMap<String, String>,
+        body: Value,
+    ) -> Result<Value, Box<dyn Error + Send + Sync>> {
+        self.respond("PUT", url, headers, Some(body))
+    }
+
+    async fn delete(
+        &self,
+        url: String,
+        headers: HashMap<String, String>,
+    ) -> Result<Value, Box<dyn Error + Send + Sync>> {
+        self.respond("DELETE", url, headers, None)
+    }
+}
+
+/// Webhook response of a successful checkin with ID 1, for tests.
+#[cfg(test)]
+pub(crate) fn success_response() -> Value {
+    serde_json::json!({
+        "status": 200,
+        "checkin": {
+            "id": 1,
+            "checked_in_at": "2021-01-01T00:00:00+09:00",
+            "note": "",
+            "link": "",
+            "tags": [],
+            "source": "webhook",
+            "is_private": false,
+            "is_too_sensitive": false,
+        },
+    })
+}
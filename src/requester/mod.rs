@@ -0,0 +1,154 @@
+//! Contains built-in implementations of `TissueRequester`.
+
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+mod fetch;
+#[cfg(any(test, feature = "test-util"))]
+mod mock;
+#[cfg(feature = "reqwest")]
+mod reqwest;
+#[cfg(feature = "surf")]
+mod surf;
+
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+pub use self::fetch::FetchRequester;
+#[cfg(test)]
+pub(crate) use self::mock::success_response;
+#[cfg(any(test, feature = "test-util"))]
+pub use self::mock::{MockRequest, MockRequester};
+#[cfg(feature = "reqwest")]
+pub use self::reqwest::ReqwestRequester;
+#[cfg(feature = "surf")]
+pub use self::surf::SurfRequester;
+
+/// Default `User-Agent` for built-in requesters.
+#[cfg(any(feature = "reqwest", feature = "surf"))]
+const USER_AGENT: &str = concat!("tissue-rs/", env!("CARGO_PKG_VERSION"));
+
+/// Decodes a response body into `Value`.
+/// An empty body is treated as an empty object, and a body other than a JSON object,
+/// such as an HTML error page of a proxy, is kept in an object as `body`.
+/// Into an object, the HTTP status code is inserted as `status` unless it already has one,
+/// and `Retry-After` header as `retry_after`.
+#[cfg(any(
+    feature = "reqwest",
+    feature = "surf",
+    all(feature = "wasm", target_arch = "wasm32")
+))]
+fn decode_body(status: u16, retry_after: Option<&str>, body: &[u8]) -> serde_json::Value {
+    use serde_json::{from_slice, Map, Value};
+
+    let mut object = Map::new();
+    match from_slice(body) {
+        Ok(Value::Object(decoded)) => object = decoded,
+        Ok(value) => {
+            object.insert("body".into(), value);
+        }
+        Err(_) if body.is_empty() => (),
+        Err(_) => {
+            let text = String::from_utf8_lossy(body);
+            object.insert("body".into(), text.into());
+        }
+    }
+    object.entry("status").or_insert_with(|| status.into());
+    if let Some(seconds) = retry_after.and_then(parse_retry_after) {
+        object.insert("retry_after".into(), seconds.into());
+    }
+
+    Value::Object(object)
+}
+
+/// Hides `url` in the message of a transport error, since it may contain a secret like webhook ID.
+/// The original error is kept as its source.
+#[cfg(any(feature = "surf", all(feature = "wasm", target_arch = "wasm32")))]
+fn without_url(
+    error: Box<dyn std::error::Error + Send + Sync + 'static>,
+    url: &str,
+) -> Box<dyn std::error::Error + Send + Sync + 'static> {
+    crate::error::RedactedError::wrap(error, url.into(), "(url hidden)".into())
+}
+
+/// Parses `Retry-After` header value, either in seconds or HTTP-date, into seconds.
+#[cfg(any(
+    feature = "reqwest",
+    feature = "surf",
+    all(feature = "wasm", target_arch = "wasm32")
+))]
+fn parse_retry_after(value: &str) -> Option<u64> {
+    use chrono::prelude::*;
+
+    let value = value.trim();
+    if let Ok(seconds) = value.parse() {
+        return Some(seconds);
+    }
+
+    let date = DateTime::parse_from_rfc2822(value).ok()?;
+    let seconds = date.signed_duration_since(Utc::now()).num_seconds();
+    Some(seconds.max(0) as u64)
+}
+
+#[cfg(all(test, any(feature = "reqwest", feature = "surf")))]
+mod tests {
+    use super::*;
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread::{self, JoinHandle},
+    };
+
+    use serde_json::json;
+
+    /// Starts a server answering one request with `status` and JSON `body`.
+    /// Returns its base URL, and the handle returning the request head and body.
+    pub(super) fn serve_once(status: &str, body: &str) -> (String, JoinHandle<(String, String)>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        let response = format!(
+            "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            body.len(),
+            body
+        );
+
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut head = String::new();
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some((key, value)) = line.split_once(':') {
+                    if key.eq_ignore_ascii_case("content-length") {
+                        content_length = value.trim().parse().unwrap();
+                    }
+                }
+                if line == "\r\n" {
+                    break;
+                }
+                head.push_str(&line);
+            }
+            let mut request_body = vec![0; content_length];
+            reader.read_exact(&mut request_body).unwrap();
+            reader.get_mut().write_all(response.as_bytes()).unwrap();
+            (head, String::from_utf8(request_body).unwrap())
+        });
+        (base, handle)
+    }
+
+    #[test]
+    fn decodes_body() {
+        assert_eq!(decode_body(204, None, b""), json!({ "status": 204 }));
+        assert_eq!(
+            decode_body(429, Some("30"), br#"{"message": "Too Many Requests"}"#),
+            json!({ "status": 429, "retry_after": 30, "message": "Too Many Requests" })
+        );
+        assert_eq!(
+            decode_body(502, None, b"<html>Bad Gateway</html>"),
+            json!({ "status": 502, "body": "<html>Bad Gateway</html>" })
+        );
+        assert_eq!(
+            decode_body(200, None, b"[1, 2]"),
+            json!({ "status": 200, "body": [1, 2] })
+        );
+    }
+}
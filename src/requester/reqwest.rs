@@ -0,0 +1,204 @@
+//! Contains `TissueRequester` implementation with reqwest.
+
+use super::{decode_body, USER_AGENT};
+use crate::TissueRequester;
+use std::{collections::HashMap, error::Error};
+
+use async_trait::async_trait;
+use reqwest::{
+    header::{HeaderMap, HeaderName, HeaderValue, RETRY_AFTER},
+    Client, Proxy, RequestBuilder,
+};
+use serde_json::Value;
+
+/// `TissueRequester` backed by `reqwest::Client`.
+/// See `TissueRequester` for the response contract.
+#[derive(Debug, Clone)]
+pub struct ReqwestRequester {
+    client: Client,
+}
+
+impl ReqwestRequester {
+    /// Creates a new requester with default `User-Agent`.
+    pub fn new() -> ReqwestRequester {
+        let client = Client::builder()
+            .user_agent(USER_AGENT)
+            .build()
+            .expect("Default client should be built");
+        ReqwestRequester { client }
+    }
+
+    /// Creates a new requester with default `User-Agent`, sending all requests through `proxy`.
+    /// SOCKS5 proxies are supported with `socks5://` or `socks5h://` scheme, and the
+    /// credentials are taken from the URL or `Proxy::basic_auth`.
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), reqwest::Error> {
+    /// use reqwest::Proxy;
+    /// use tissue_rs::ReqwestRequester;
+    ///
+    /// let proxy = Proxy::all("socks5h://127.0.0.1:1080")?.basic_auth("user", "password");
+    /// let requester = ReqwestRequester::with_proxy(proxy)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// To check it manually against a local SOCKS5 server, e.g. with OpenSSH:
+    ///
+    /// 1. Start the server with `ssh -N -D 1080 localhost`.
+    /// 2. Send a checkin through `socks5h://127.0.0.1:1080` to `https://shikorism.net`.
+    /// 3. Stop the server and confirm that sending fails with `TissueError::Transport`.
+    pub fn with_proxy(proxy: Proxy) -> Result<ReqwestRequester, reqwest::Error> {
+        let client = Client::builder()
+            .user_agent(USER_AGENT)
+            .proxy(proxy)
+            .build()?;
+        Ok(ReqwestRequester { client })
+    }
+
+    /// Creates a new requester with pre-built client.
+    pub fn with_client(client: Client) -> ReqwestRequester {
+        ReqwestRequester { client }
+    }
+
+    async fn send(
+        &self,
+        request: RequestBuilder,
+        headers: HashMap<String, String>,
+    ) -> Result<Value, Box<dyn Error + Send + Sync>> {
+        // Replaces the default ones like `Content-Type` set by `json`, instead of appending
+        let mut header_map = HeaderMap::new();
+        for (key, value) in &headers {
+            header_map.insert(
+                HeaderName::from_bytes(key.as_bytes())?,
+                HeaderValue::from_str(value)?,
+            );
+        }
+        // The URL in errors may have a secret like webhook ID
+        let response = request
+            .headers(header_map)
+            .send()
+            .await
+            .map_err(reqwest::Error::without_url)?;
+        let status = response.status().as_u16();
+        let retry_after = response
+            .headers()
+            .get(RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_owned);
+        let body = response
+            .bytes()
+            .await
+            .map_err(reqwest::Error::without_url)?;
+
+        Ok(decode_body(status, retry_after.as_deref(), &body))
+    }
+}
+
+impl Default for ReqwestRequester {
+    fn default() -> ReqwestRequester {
+        ReqwestRequester::new()
+    }
+}
+
+#[async_trait]
+impl TissueRequester for ReqwestRequester {
+    async fn get(
+        &self,
+        url: String,
+        headers: HashMap<String, String>,
+    ) -> Result<Value, Box<dyn Error + Send + Sync>> {
+        let request = self.client.get(&url);
+        self.send(request, headers).await
+    }
+
+    async fn post(
+        &self,
+        url: String,
+        headers: HashMap<String, String>,
+        body: Value,
+    ) -> Result<Value, Box<dyn Error + Send + Sync>> {
+        let request = self.client.post(&url).json(&body);
+        self.send(request, headers).await
+    }
+
+    async fn put(
+        &self,
+        url: String,
+        headers: HashMap<String, String>,
+        body: Value,
+    ) -> Result<Value, Box<dyn Error + Send + Sync>> {
+        let request = self.client.put(&url).json(&body);
+        self.send(request, headers).await
+    }
+
+    async fn delete(
+        &self,
+        url: String,
+        headers: HashMap<String, String>,
+    ) -> Result<Value, Box<dyn Error + Send + Sync>> {
+        let request = self.client.delete(&url);
+        self.send(request, headers).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        requester::tests::serve_once, Checkin, CheckinResponse, IncomingEndpoint, ReqwestRequester,
+    };
+    use std::error::Error;
+
+    #[tokio::test]
+    async fn sends_checkin_to_server() {
+        let body = r#"{
+            "status": 200,
+            "checkin": {
+                "id": 1,
+                "checked_in_at": "2021-01-01T00:00:00+09:00",
+                "tags": ["anime"],
+                "source": "webhook",
+                "is_private": false,
+                "is_too_sensitive": false
+            }
+        }"#;
+        let (base, server) = serve_once("200 OK", body);
+        let endpoint = IncomingEndpoint::with_base_url(&base, "id", ReqwestRequester::new())
+            .unwrap()
+            .with_header("X-Client", "test");
+
+        let mut builder = Checkin::builder();
+        builder.tags(["anime"]).unwrap();
+        let response = endpoint.send_checkin(&builder.build()).await.unwrap();
+        assert!(matches!(response, CheckinResponse::Success(_)));
+
+        let (head, request_body) = server.join().unwrap();
+        assert!(head.starts_with("POST /api/webhooks/checkin/id HTTP/1.1"));
+        let head = head.to_lowercase();
+        assert!(head.contains("x-client: test"));
+        assert!(head.contains("user-agent: tissue-rs/"));
+        let request_body: serde_json::Value = serde_json::from_str(&request_body).unwrap();
+        assert_eq!(request_body["tags"], serde_json::json!(["anime"]));
+    }
+
+    #[tokio::test]
+    async fn transport_error_hides_webhook_id() {
+        // Nothing listens on port 1
+        let endpoint = IncomingEndpoint::with_base_url(
+            "http://127.0.0.1:1",
+            "SECRETID",
+            ReqwestRequester::new(),
+        )
+        .unwrap();
+        let error = endpoint
+            .send_checkin(&Checkin::builder().build())
+            .await
+            .unwrap_err();
+
+        let mut current: Option<&(dyn Error + 'static)> = Some(&error);
+        while let Some(inner) = current {
+            assert!(!inner.to_string().contains("SECRETID"), "{}", inner);
+            current = inner.source();
+        }
+    }
+}
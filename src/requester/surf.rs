@@ -0,0 +1,138 @@
+//! Contains `TissueRequester` implementation with surf.
+
+use super::{decode_body, without_url, USER_AGENT};
+use crate::TissueRequester;
+use std::{collections::HashMap, convert::TryInto, error::Error};
+
+use async_trait::async_trait;
+use serde_json::Value;
+use surf::{Client, Config, RequestBuilder};
+
+/// `TissueRequester` backed by `surf::Client`.
+/// See `TissueRequester` for the response contract.
+#[derive(Debug, Clone)]
+pub struct SurfRequester {
+    client: Client,
+}
+
+impl SurfRequester {
+    /// Creates a new requester with default `User-Agent`.
+    pub fn new() -> SurfRequester {
+        let config = Config::new()
+            .add_header("User-Agent", USER_AGENT)
+            .expect("User-Agent should be valid");
+        let client = config.try_into().expect("Default client should be built");
+        SurfRequester { client }
+    }
+
+    /// Creates a new requester with pre-built client.
+    pub fn with_client(client: Client) -> SurfRequester {
+        SurfRequester { client }
+    }
+
+    async fn send(
+        &self,
+        request: RequestBuilder,
+        headers: HashMap<String, String>,
+    ) -> Result<Value, Box<dyn Error + Send + Sync>> {
+        let request = headers.iter().fold(request, |request, (key, value)| {
+            request.header(key.as_str(), value.as_str())
+        });
+        let mut response = self.client.send(request).await?;
+        let status = response.status().into();
+        let retry_after = response
+            .header("Retry-After")
+            .map(|values| values.last().as_str().to_owned());
+        let body = response.body_bytes().await?;
+
+        Ok(decode_body(status, retry_after.as_deref(), &body))
+    }
+}
+
+impl Default for SurfRequester {
+    fn default() -> SurfRequester {
+        SurfRequester::new()
+    }
+}
+
+#[async_trait]
+impl TissueRequester for SurfRequester {
+    async fn get(
+        &self,
+        url: String,
+        headers: HashMap<String, String>,
+    ) -> Result<Value, Box<dyn Error + Send + Sync>> {
+        let request = self.client.get(&url);
+        self.send(request, headers)
+            .await
+            .map_err(|error| without_url(error, &url))
+    }
+
+    async fn post(
+        &self,
+        url: String,
+        headers: HashMap<String, String>,
+        body: Value,
+    ) -> Result<Value, Box<dyn Error + Send + Sync>> {
+        let request = self.client.post(&url).body_json(&body)?;
+        self.send(request, headers)
+            .await
+            .map_err(|error| without_url(error, &url))
+    }
+
+    async fn put(
+        &self,
+        url: String,
+        headers: HashMap<String, String>,
+        body: Value,
+    ) -> Result<Value, Box<dyn Error + Send + Sync>> {
+        let request = self.client.put(&url).body_json(&body)?;
+        self.send(request, headers)
+            .await
+            .map_err(|error| without_url(error, &url))
+    }
+
+    async fn delete(
+        &self,
+        url: String,
+        headers: HashMap<String, String>,
+    ) -> Result<Value, Box<dyn Error + Send + Sync>> {
+        let request = self.client.delete(&url);
+        self.send(request, headers)
+            .await
+            .map_err(|error| without_url(error, &url))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        requester::tests::serve_once, Checkin, CheckinResponse, IncomingEndpoint, SurfRequester,
+    };
+
+    use futures::executor::block_on;
+
+    #[test]
+    fn parses_validation_error() {
+        let body = r#"{
+            "status": 422,
+            "error": { "message": "Validation failed", "violations": ["note is too long"] }
+        }"#;
+        let (base, server) = serve_once("422 Unprocessable Entity", body);
+        let endpoint = IncomingEndpoint::with_base_url(&base, "id", SurfRequester::new())
+            .unwrap()
+            .with_header("X-Client", "test");
+
+        let response = block_on(endpoint.send_checkin(&Checkin::builder().build())).unwrap();
+        match response {
+            CheckinResponse::ValidationError(violations) => {
+                assert_eq!(violations[0].message(), "note is too long")
+            }
+            response => panic!("Unexpected response: {:?}", response),
+        }
+
+        let (head, _) = server.join().unwrap();
+        assert!(head.starts_with("POST /api/webhooks/checkin/id HTTP/1.1"));
+        assert!(head.to_lowercase().contains("x-client: test"));
+    }
+}
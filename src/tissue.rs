@@ -1,102 +1,2120 @@
 //! Contains types corresponding Tissue service.
 
-use crate::{checkin::Checkin, TissueRequester};
-use std::{collections::HashMap, error::Error};
+#[cfg(not(feature = "full-debug"))]
+use crate::checkin::Redacted;
+use crate::{
+    checkin::Checkin,
+    error::{RedactedError, TissueError},
+    query::percent_encode,
+    TissueRequester,
+};
+#[cfg(not(feature = "full-debug"))]
+use std::fmt::{Debug, Formatter, Result as FmtResult};
+use std::{
+    borrow::Cow,
+    cmp::Ordering,
+    collections::{hash_map::RandomState, HashMap},
+    error::Error,
+    future::Future,
+    hash::{BuildHasher, Hash, Hasher},
+    mem,
+    net::Ipv6Addr,
+    ops::Deref,
+    slice,
+    sync::{
+        atomic::{AtomicU64, Ordering as AtomicOrdering},
+        Mutex,
+    },
+    time::Duration,
+};
 
 use chrono::prelude::*;
+use futures::{
+    future::{select, Either, FutureExt, Shared},
+    stream::{self, StreamExt},
+};
+use futures_timer::Delay;
 use serde::Deserialize;
-use serde_json::{from_value, to_value, Value};
+use serde_json::{from_value, Value};
 
 /// Returned checkin data for successful checkim request.
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize)]
+/// camelCase keys returned by some older instances are also accepted.
+/// Equality and hash cover all fields; wrap it in `ById` to compare by ID only.
+/// `Debug` shows only the lengths of note and link unless `full-debug` feature is enabled.
+#[derive(Clone, PartialEq, Eq, Hash, Deserialize)]
+#[cfg_attr(feature = "full-debug", derive(Debug))]
 pub struct ReceivedCheckin {
     id: usize,
+    #[serde(alias = "checkedInAt")]
     checked_in_at: DateTime<Local>,
-    note: String,
-    link: String,
+    #[serde(default)]
+    note: Option<String>,
+    #[serde(default)]
+    link: Option<String>,
     tags: Vec<String>,
     source: String,
+    #[serde(alias = "isPrivate")]
+    is_private: bool,
+    #[serde(alias = "isTooSensitive")]
+    is_too_sensitive: bool,
+}
+
+impl ReceivedCheckin {
+    /// ID of checkin.
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    /// Timestamp of checkin.
+    pub fn checked_in_at(&self) -> &DateTime<Local> {
+        &self.checked_in_at
+    }
+
+    /// Notes.
+    pub fn note(&self) -> Option<&String> {
+        self.note.as_ref()
+    }
+
+    /// Link.
+    pub fn link(&self) -> Option<&String> {
+        self.link.as_ref()
+    }
+
+    /// Tag(s).
+    pub fn tags(&self) -> impl Iterator<Item = &String> {
+        self.tags.iter()
+    }
+
+    /// Source of checkin.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Kind of the source of checkin.
+    pub fn source_kind(&self) -> SourceKind {
+        SourceKind::from(self.source.as_str())
+    }
+
+    /// Whether it is private or not.
+    pub fn is_private(&self) -> bool {
+        self.is_private
+    }
+
+    /// Whether it is too sensitive or not.
+    pub fn is_too_sensitive(&self) -> bool {
+        self.is_too_sensitive
+    }
+}
+
+#[cfg(not(feature = "full-debug"))]
+impl Debug for ReceivedCheckin {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("ReceivedCheckin")
+            .field("id", &self.id)
+            .field("checked_in_at", &self.checked_in_at)
+            .field("note", &Redacted(self.note.as_deref()))
+            .field("link", &Redacted(self.link.as_deref()))
+            .field("tags", &self.tags)
+            .field("source", &self.source)
+            .field("is_private", &self.is_private)
+            .field("is_too_sensitive", &self.is_too_sensitive)
+            .finish()
+    }
+}
+
+impl PartialOrd for ReceivedCheckin {
+    fn partial_cmp(&self, other: &ReceivedCheckin) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ReceivedCheckin {
+    /// Orders by timestamp, then by ID.
+    /// Other fields are compared last to stay consistent with `Eq`.
+    fn cmp(&self, other: &ReceivedCheckin) -> Ordering {
+        self.checked_in_at
+            .cmp(&other.checked_in_at)
+            .then_with(|| self.id.cmp(&other.id))
+            .then_with(|| self.note.cmp(&other.note))
+            .then_with(|| self.link.cmp(&other.link))
+            .then_with(|| self.tags.cmp(&other.tags))
+            .then_with(|| self.source.cmp(&other.source))
+            .then_with(|| self.is_private.cmp(&other.is_private))
+            .then_with(|| self.is_too_sensitive.cmp(&other.is_too_sensitive))
+    }
+}
+
+/// `ReceivedCheckin` compared and hashed by its ID only, for deduplicating fetched checkins.
+/// Unlike `ReceivedCheckin` itself, the same checkin with differing fields is equal.
+#[derive(Debug, Clone)]
+pub struct ById(pub ReceivedCheckin);
+
+impl ById {
+    /// Returns the wrapped checkin.
+    pub fn into_inner(self) -> ReceivedCheckin {
+        self.0
+    }
+}
+
+impl Deref for ById {
+    type Target = ReceivedCheckin;
+
+    fn deref(&self) -> &ReceivedCheckin {
+        &self.0
+    }
+}
+
+impl PartialEq for ById {
+    fn eq(&self, other: &ById) -> bool {
+        self.0.id == other.0.id
+    }
+}
+
+impl Eq for ById {}
+
+impl Hash for ById {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.id.hash(state);
+    }
+}
+
+/// Borrowed version of `ReceivedCheckin`, deserialized with strings borrowed from the input if possible.
+/// `Debug` is redacted in the same way as `ReceivedCheckin`.
+#[derive(Clone, PartialEq, Eq, Hash, Deserialize)]
+#[cfg_attr(feature = "full-debug", derive(Debug))]
+pub struct ReceivedCheckinRef<'a> {
+    id: usize,
+    #[serde(alias = "checkedInAt")]
+    checked_in_at: DateTime<Local>,
+    #[serde(borrow, default)]
+    note: Option<CowStr<'a>>,
+    #[serde(borrow, default)]
+    link: Option<CowStr<'a>>,
+    #[serde(borrow)]
+    tags: Vec<CowStr<'a>>,
+    #[serde(borrow)]
+    source: CowStr<'a>,
+    #[serde(alias = "isPrivate")]
     is_private: bool,
+    #[serde(alias = "isTooSensitive")]
     is_too_sensitive: bool,
 }
 
+#[cfg(not(feature = "full-debug"))]
+impl Debug for ReceivedCheckinRef<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.debug_struct("ReceivedCheckinRef")
+            .field("id", &self.id)
+            .field("checked_in_at", &self.checked_in_at)
+            .field("note", &Redacted(self.note()))
+            .field("link", &Redacted(self.link()))
+            .field("tags", &self.tags().collect::<Vec<_>>())
+            .field("source", &self.source())
+            .field("is_private", &self.is_private)
+            .field("is_too_sensitive", &self.is_too_sensitive)
+            .finish()
+    }
+}
+
+/// String borrowed from the input unless it has escapes.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize)]
+#[serde(transparent)]
+struct CowStr<'a>(#[serde(borrow)] Cow<'a, str>);
+
+impl<'a> ReceivedCheckinRef<'a> {
+    /// ID of checkin.
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    /// Timestamp of checkin.
+    pub fn checked_in_at(&self) -> &DateTime<Local> {
+        &self.checked_in_at
+    }
+
+    /// Notes.
+    pub fn note(&self) -> Option<&str> {
+        self.note.as_ref().map(|note| note.0.as_ref())
+    }
+
+    /// Link.
+    pub fn link(&self) -> Option<&str> {
+        self.link.as_ref().map(|link| link.0.as_ref())
+    }
+
+    /// Tag(s).
+    pub fn tags(&self) -> impl Iterator<Item = &str> {
+        self.tags.iter().map(|tag| tag.0.as_ref())
+    }
+
+    /// Source of checkin.
+    pub fn source(&self) -> &str {
+        &self.source.0
+    }
+
+    /// Kind of the source of checkin.
+    pub fn source_kind(&self) -> SourceKind {
+        SourceKind::from(self.source())
+    }
+
+    /// Whether it is private or not.
+    pub fn is_private(&self) -> bool {
+        self.is_private
+    }
+
+    /// Whether it is too sensitive or not.
+    pub fn is_too_sensitive(&self) -> bool {
+        self.is_too_sensitive
+    }
+
+    /// Converts into owned `ReceivedCheckin`.
+    pub fn into_owned(self) -> ReceivedCheckin {
+        ReceivedCheckin {
+            id: self.id,
+            checked_in_at: self.checked_in_at,
+            note: self.note.map(|note| note.0.into_owned()),
+            link: self.link.map(|link| link.0.into_owned()),
+            tags: self
+                .tags
+                .into_iter()
+                .map(|tag| tag.0.into_owned())
+                .collect(),
+            source: self.source.0.into_owned(),
+            is_private: self.is_private,
+            is_too_sensitive: self.is_too_sensitive,
+        }
+    }
+}
+
+/// Kind of the source of a received checkin.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum SourceKind {
+    /// Checked in on the website
+    Web,
+
+    /// Imported from CSV
+    Csv,
+
+    /// Sent through Incoming Webhook
+    Webhook,
+
+    /// Created through the API
+    Api,
+
+    /// Unknown source, as given
+    Other(String),
+}
+
+impl SourceKind {
+    /// Source string as Tissue uses.
+    pub fn as_str(&self) -> &str {
+        match self {
+            SourceKind::Web => "web",
+            SourceKind::Csv => "csv",
+            SourceKind::Webhook => "webhook",
+            SourceKind::Api => "api",
+            SourceKind::Other(source) => source,
+        }
+    }
+}
+
+impl From<&str> for SourceKind {
+    fn from(source: &str) -> SourceKind {
+        match source {
+            "web" => SourceKind::Web,
+            "csv" => SourceKind::Csv,
+            "webhook" => SourceKind::Webhook,
+            "api" => SourceKind::Api,
+            other => SourceKind::Other(other.into()),
+        }
+    }
+}
+
+/// Describes a validation violation in the checkin request.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Violation {
+    field: Option<String>,
+    message: String,
+}
+
+impl Violation {
+    /// Field name that caused the violation, if known.
+    pub fn field(&self) -> Option<&str> {
+        self.field.as_deref()
+    }
+
+    /// Violation message.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
 /// Represents a response from Tissue checkin.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
 pub enum CheckinResponse {
     /// Success
     Success(ReceivedCheckin),
 
     /// Validation error occurred
-    ValidationError(Vec<String>),
+    ValidationError(Vec<Violation>),
 
     /// Other error occurred
-    OtherError(String),
+    OtherError {
+        /// HTTP status code
+        status: u16,
+
+        /// Error message
+        message: String,
+    },
+
+    /// Authentication failed or was not permitted
+    Unauthorized {
+        /// HTTP status code, 401 or 403
+        status: u16,
+
+        /// Error message
+        message: String,
+    },
+
+    /// Too many requests were sent
+    RateLimited {
+        /// Time to wait before next request, if told
+        retry_after: Option<Duration>,
+    },
+}
+
+impl CheckinResponse {
+    /// Checks whether the checkin succeeded.
+    pub fn is_success(&self) -> bool {
+        matches!(self, CheckinResponse::Success(_))
+    }
+
+    /// Returns the received checkin if succeeded.
+    pub fn as_received(&self) -> Option<&ReceivedCheckin> {
+        match self {
+            CheckinResponse::Success(checkin) => Some(checkin),
+            _ => None,
+        }
+    }
+
+    /// Converts into the received checkin if succeeded.
+    pub fn into_received(self) -> Option<ReceivedCheckin> {
+        match self {
+            CheckinResponse::Success(checkin) => Some(checkin),
+            _ => None,
+        }
+    }
+
+    /// Name of the variant, for diagnostics.
+    #[cfg(any(feature = "log", feature = "tracing"))]
+    pub(crate) fn kind(&self) -> &'static str {
+        match self {
+            CheckinResponse::Success(_) => "Success",
+            CheckinResponse::ValidationError(_) => "ValidationError",
+            CheckinResponse::OtherError { .. } => "OtherError",
+            CheckinResponse::Unauthorized { .. } => "Unauthorized",
+            CheckinResponse::RateLimited { .. } => "RateLimited",
+        }
+    }
 }
 
 /// Represents an endpoint for Incoming Webhook.
 pub struct IncomingEndpoint<T> {
+    scheme: &'static str,
     domain: String,
+    base_path: String,
     id: String,
+    headers: HashMap<String, String>,
+    query: Vec<(String, String)>,
     requester: T,
+    timeout: Option<Duration>,
+    retries: u32,
+    backoff: Duration,
+    source: Option<String>,
+    idempotency_keys: bool,
+    jitter: Option<AtomicU64>,
 }
 
 impl<T: TissueRequester> IncomingEndpoint<T> {
     /// Creates a new endpoint with ID.
+    /// The ID is not validated, so a mistyped one results in a broken URL. See `try_new`.
     pub fn new(id: &str, requester: T) -> IncomingEndpoint<T> {
-        IncomingEndpoint {
-            domain: "shikorism.net".into(),
-            id: id.into(),
-            requester,
-        }
+        IncomingEndpoint::with_domain("shikorism.net", id, requester)
+    }
+
+    /// Creates a new endpoint with ID.
+    /// Returns `Err(TissueError::InvalidId)` if the ID is empty or has characters other than
+    /// alphanumerics, `-` and `_`.
+    pub fn try_new(id: &str, requester: T) -> Result<IncomingEndpoint<T>, TissueError> {
+        check_id(id)?;
+        Ok(IncomingEndpoint::new(id, requester))
     }
 
     /// Creates a new endpoint with domain and ID.
+    /// The ID is not validated, so a mistyped one results in a broken URL. See `try_with_domain`.
     pub fn with_domain(domain: &str, id: &str, requester: T) -> IncomingEndpoint<T> {
         IncomingEndpoint {
+            scheme: "https",
             domain: domain.into(),
+            base_path: String::new(),
             id: id.into(),
+            headers: HashMap::new(),
+            query: vec![],
             requester,
+            timeout: None,
+            retries: 0,
+            backoff: Duration::from_secs(0),
+            source: None,
+            idempotency_keys: false,
+            jitter: None,
+        }
+    }
+
+    /// Creates a new endpoint with domain and ID.
+    /// Returns `Err(TissueError::InvalidUrl)` unless the domain is a host with optional port,
+    /// and `Err(TissueError::InvalidId)` for an invalid ID as `try_new` does.
+    pub fn try_with_domain(
+        domain: &str,
+        id: &str,
+        requester: T,
+    ) -> Result<IncomingEndpoint<T>, TissueError> {
+        if !is_valid_authority(domain) {
+            return Err(TissueError::InvalidUrl(domain.into()));
+        }
+        check_id(id)?;
+        Ok(IncomingEndpoint::with_domain(domain, id, requester))
+    }
+
+    /// Creates a new endpoint with base URL like `http://localhost:8000` and ID.
+    /// A path in the URL is used as the base path, as `with_base_path` does.
+    /// Returns `Err(TissueError::InvalidUrl)` unless it is an http(s) URL with only host,
+    /// optional port and path, and `Err(TissueError::InvalidId)` for an invalid ID as `try_new` does.
+    pub fn with_base_url(
+        base: &str,
+        id: &str,
+        requester: T,
+    ) -> Result<IncomingEndpoint<T>, TissueError> {
+        let invalid = || TissueError::InvalidUrl(base.into());
+        let (scheme, rest) = match base.split_once("://") {
+            Some(("http", rest)) => ("http", rest),
+            Some(("https", rest)) => ("https", rest),
+            _ => return Err(invalid()),
+        };
+        let (domain, base_path) = rest.split_once('/').unwrap_or((rest, ""));
+        if !is_valid_authority(domain) || !is_valid_path(base_path) {
+            return Err(invalid());
+        }
+
+        check_id(id)?;
+
+        let endpoint = IncomingEndpoint::with_domain(domain, id, requester);
+        Ok(IncomingEndpoint {
+            scheme,
+            ..endpoint.with_base_path(base_path)
+        })
+    }
+
+    /// Creates a new endpoint from the full webhook URL shown in Tissue,
+    /// like `https://shikorism.net/api/webhooks/checkin/XXXX`.
+    /// Returns `Err(TissueError::InvalidUrl)` if it is not a webhook URL,
+    /// and validates the rest as `with_base_url` does.
+    pub fn from_webhook_url(url: &str, requester: T) -> Result<IncomingEndpoint<T>, TissueError> {
+        let (base, id) = url
+            .trim()
+            .rsplit_once("/api/webhooks/checkin/")
+            .ok_or_else(|| TissueError::InvalidUrl(url.into()))?;
+        let id = id.strip_suffix('/').unwrap_or(id);
+        IncomingEndpoint::with_base_url(base, id, requester)
+    }
+
+    /// Domain of Tissue instance.
+    pub fn domain(&self) -> &str {
+        &self.domain
+    }
+
+    /// Webhook ID.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Mutable reference to the requester.
+    pub fn requester_mut(&mut self) -> &mut T {
+        &mut self.requester
+    }
+
+    /// Consumes the endpoint and returns the requester.
+    pub fn into_requester(self) -> T {
+        self.requester
+    }
+
+    /// Sets the timeout for each request.
+    /// Requests exceeding it fail with `TissueError::Timeout`.
+    pub fn with_timeout(self, timeout: Duration) -> IncomingEndpoint<T> {
+        IncomingEndpoint {
+            timeout: Some(timeout),
+            ..self
+        }
+    }
+
+    /// Sets the retry policy. Failed requests are retried up to `retries` times,
+    /// waiting `backoff` doubled for each attempt in between.
+    /// Only transport errors, timeouts, 429 and 5xx responses are retried.
+    /// For 429 responses, it waits at least `retry_after` if told.
+    pub fn with_retries(self, retries: u32, backoff: Duration) -> IncomingEndpoint<T> {
+        IncomingEndpoint {
+            retries,
+            backoff,
+            ..self
+        }
+    }
+
+    /// Randomizes each wait of the retry policy between zero and the doubled `backoff`
+    /// (full jitter), so that concurrent retries do not happen at once.
+    /// The same `seed` gives the same sequence of waits.
+    pub fn with_jitter(self, seed: u64) -> IncomingEndpoint<T> {
+        IncomingEndpoint {
+            jitter: Some(AtomicU64::new(seed)),
+            ..self
+        }
+    }
+
+    /// Sets the path prefix inserted before `/api`, for instances behind a reverse proxy.
+    /// Leading and trailing slashes are ignored, so `tissue` and `/tissue/` are the same.
+    /// The path is not validated, so one with spaces, `?` or `#` results in a broken URL.
+    /// See `try_with_base_path`.
+    pub fn with_base_path(self, base_path: &str) -> IncomingEndpoint<T> {
+        let base_path = base_path.trim_matches('/');
+        IncomingEndpoint {
+            base_path: if base_path.is_empty() {
+                String::new()
+            } else {
+                format!("/{}", base_path)
+            },
+            ..self
+        }
+    }
+
+    /// Sets the path prefix inserted before `/api` as `with_base_path` does.
+    /// Returns `Err(TissueError::InvalidUrl)` if it has characters other than visible ASCII,
+    /// or any of `?`, `#` and `\`.
+    pub fn try_with_base_path(self, base_path: &str) -> Result<IncomingEndpoint<T>, TissueError> {
+        if !is_valid_path(base_path) {
+            return Err(TissueError::InvalidUrl(base_path.into()));
+        }
+        Ok(self.with_base_path(base_path))
+    }
+
+    /// Adds a header sent with every request.
+    pub fn with_header(mut self, key: &str, value: &str) -> IncomingEndpoint<T> {
+        self.headers.insert(key.into(), value.into());
+        self
+    }
+
+    /// Sets `Content-Type` header of requests, instead of default `application/json`.
+    pub fn with_content_type(self, content_type: &str) -> IncomingEndpoint<T> {
+        self.with_header("Content-Type", content_type)
+    }
+
+    /// Adds a query parameter appended to the webhook URL, percent-encoded.
+    /// Parameters are appended in the order added, and the same key can be added more than once.
+    pub fn with_query_param(mut self, key: &str, value: &str) -> IncomingEndpoint<T> {
+        self.query.push((key.into(), value.into()));
+        self
+    }
+
+    /// Sends `Idempotency-Key` header generated for each checkin,
+    /// so that a retried request is not recorded twice.
+    /// Keys are unique within the process, but not guaranteed to be unpredictable.
+    pub fn with_idempotency_keys(self) -> IncomingEndpoint<T> {
+        IncomingEndpoint {
+            idempotency_keys: true,
+            ..self
+        }
+    }
+
+    /// Sets the default source, used for checkins without their own source.
+    pub fn with_source(self, source: &str) -> IncomingEndpoint<T> {
+        IncomingEndpoint {
+            source: Some(source.into()),
+            ..self
         }
     }
 
     /// Sends a checkin.
-    pub async fn send_checkin(
-        &mut self,
+    /// With `with_idempotency_keys`, a new key is generated and reused for the retries.
+    ///
+    /// With `log` feature, the request is logged at `debug` level and failures at `warn` level.
+    /// With `tracing` feature, it runs in a span with `domain` and `kind` of the response.
+    /// Logs and spans never contain the webhook ID, note and link.
+    pub async fn send_checkin(&self, checkin: &Checkin) -> Result<CheckinResponse, TissueError> {
+        self.send(checkin, self.new_idempotency_key(), HashMap::new())
+            .await
+    }
+
+    /// Sends a checkin with `Idempotency-Key` header, reused for the retries.
+    pub async fn send_checkin_with_idempotency_key(
+        &self,
         checkin: &Checkin,
-    ) -> Result<CheckinResponse, Box<dyn Error + Send + Sync + 'static>> {
-        let target_url = format!("https://{}/api/webhooks/checkin/{}", self.domain, self.id);
-        let response: Value = self
-            .requester
-            .post(target_url, HashMap::new(), to_value(checkin)?)
-            .await?;
+        key: &str,
+    ) -> Result<CheckinResponse, TissueError> {
+        self.send(checkin, Some(key.into()), HashMap::new()).await
+    }
 
-        parse_response(&response)
+    /// Sends a checkin with `extra` headers added for this request only.
+    /// They take precedence over the headers of the endpoint and `Idempotency-Key`.
+    pub async fn send_checkin_with_headers(
+        &self,
+        checkin: &Checkin,
+        extra: HashMap<String, String>,
+    ) -> Result<CheckinResponse, TissueError> {
+        self.send(checkin, self.new_idempotency_key(), extra).await
     }
+
+    /// Serializes a checkin into the body `send_checkin` sends, with the default source applied.
+    pub fn checkin_body(&self, checkin: &Checkin) -> Result<Value, TissueError> {
+        let mut body = checkin.to_webhook_value();
+        if let (Some(source), None) = (&self.source, checkin.source()) {
+            body["source"] = source.as_str().into();
+        }
+        Ok(body)
+    }
+
+    /// Prepares a checkin as `send_checkin` does without sending it.
+    /// Returns the target URL and the body.
+    pub fn send_checkin_dry_run(&self, checkin: &Checkin) -> Result<(String, Value), TissueError> {
+        Ok((self.target_url(), self.checkin_body(checkin)?))
+    }
+
+    /// Sends checkins with up to `concurrency` requests in flight.
+    /// Results are in the same order as `checkins`.
+    /// Each checkin is retried as `send_checkin` does.
+    ///
+    /// Once a checkin results in `CheckinResponse::RateLimited`, checkins not sent yet wait
+    /// for `retry_after` (or the backoff of the retry policy if longer) even without retries.
+    /// The rate-limited checkin itself is not sent again unless retried by the policy.
+    pub async fn send_checkins(
+        &self,
+        checkins: &[Checkin],
+        concurrency: usize,
+    ) -> Vec<Result<CheckinResponse, TissueError>> {
+        let pause: Mutex<Option<Shared<Delay>>> = Mutex::new(None);
+        let pause = &pause;
+        let mut results: Vec<_> = stream::iter(checkins.iter().enumerate())
+            .map(|(index, checkin)| async move {
+                let current_pause = pause.lock().expect("Pause should not be poisoned").clone();
+                if let Some(current_pause) = current_pause {
+                    current_pause.await;
+                }
+
+                let result = self.send_checkin(checkin).await;
+                if let Ok(CheckinResponse::RateLimited { retry_after }) = &result {
+                    let delay = self.retry_delay(0, *retry_after);
+                    *pause.lock().expect("Pause should not be poisoned") =
+                        Some(Delay::new(delay).shared());
+                }
+                (index, result)
+            })
+            .buffer_unordered(concurrency.max(1))
+            .collect()
+            .await;
+
+        results.sort_by_key(|(index, _)| *index);
+        results.into_iter().map(|(_, result)| result).collect()
+    }
+
+    /// Sends a checkin with optional idempotency key and extra headers.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "send_checkin", skip_all, fields(domain = %self.domain, kind))
+    )]
+    async fn send(
+        &self,
+        checkin: &Checkin,
+        idempotency_key: Option<String>,
+        extra: HashMap<String, String>,
+    ) -> Result<CheckinResponse, TissueError> {
+        let target_url = self.target_url();
+        let body = self.checkin_body(checkin)?;
+        let mut headers = self.headers.clone();
+        if let Some(key) = idempotency_key {
+            headers.insert("Idempotency-Key".into(), key);
+        }
+        headers.extend(extra);
+
+        #[cfg(feature = "log")]
+        log::debug!("Sending checkin to {}", self.webhook_url("***"));
+        let response = self.post(target_url, headers, body).await?;
+        #[cfg(feature = "log")]
+        let status = response["status"].clone();
+        #[cfg(feature = "log")]
+        log::debug!("Received status {}", status);
+
+        let result = parse_response(response);
+        // The error may have the response body, which can echo the note and link
+        #[cfg(feature = "log")]
+        match &result {
+            Ok(response) => log::debug!("Checkin resulted in {}", response.kind()),
+            Err(error) => log::warn!("Checkin failed with {}, status {}", error.kind(), status),
+        }
+        result
+    }
+
+    /// Wait before the retry of `attempt`, but at least `retry_after` if told.
+    fn retry_delay(&self, attempt: u32, retry_after: Option<Duration>) -> Duration {
+        let delay = self.backoff_delay(attempt);
+        retry_after.map_or(delay, |floor| delay.max(floor))
+    }
+
+    /// Wait before the retry of `attempt`, with jitter if enabled.
+    fn backoff_delay(&self, attempt: u32) -> Duration {
+        let max = self
+            .backoff
+            .checked_mul(2u32.saturating_pow(attempt))
+            .unwrap_or(Duration::MAX);
+        let state = match &self.jitter {
+            Some(state) => state,
+            None => return max,
+        };
+
+        // SplitMix64, advancing the shared state atomically for concurrent retries
+        let mut random = state
+            .fetch_add(SPLITMIX_GAMMA, AtomicOrdering::Relaxed)
+            .wrapping_add(SPLITMIX_GAMMA);
+        random = (random ^ (random >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        random = (random ^ (random >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        random ^= random >> 31;
+
+        let ratio = (random >> 11) as f64 / (1u64 << 53) as f64;
+        Duration::try_from_secs_f64(max.as_secs_f64() * ratio).unwrap_or(max)
+    }
+
+    /// Generates a new idempotency key if enabled.
+    fn new_idempotency_key(&self) -> Option<String> {
+        if !self.idempotency_keys {
+            return None;
+        }
+
+        // std seeds `RandomState` randomly once per thread and increments the keys for each
+        // `new`, so the hashes are unique within the process but derived from predictable state
+        let mut key = String::new();
+        for _ in 0..2 {
+            let hash = RandomState::new().build_hasher().finish();
+            key.push_str(&format!("{:016x}", hash));
+        }
+        Some(key)
+    }
+
+    /// URL of the webhook.
+    fn target_url(&self) -> String {
+        self.webhook_url(&self.id)
+    }
+
+    /// URL of the webhook with specified ID, used to hide the real one.
+    fn webhook_url(&self, id: &str) -> String {
+        let mut url = format!(
+            "{}://{}{}/api/webhooks/checkin/{}",
+            self.scheme, self.domain, self.base_path, id
+        );
+        for (index, (key, value)) in self.query.iter().enumerate() {
+            let separator = if index == 0 { '?' } else { '&' };
+            url.push(separator);
+            url.push_str(&percent_encode(key));
+            url.push('=');
+            url.push_str(&percent_encode(value));
+        }
+
+        url
+    }
+
+    /// Does a POST request, applying the timeout and the retry policy.
+    async fn post(
+        &self,
+        mut url: String,
+        mut headers: HashMap<String, String>,
+        mut body: Value,
+    ) -> Result<Value, TissueError> {
+        let mut attempt = 0;
+        loop {
+            // The last attempt takes them, so that nothing is cloned without retries
+            let request = if attempt >= self.retries {
+                self.requester
+                    .post(mem::take(&mut url), mem::take(&mut headers), body.take())
+            } else {
+                self.requester
+                    .post(url.clone(), headers.clone(), body.clone())
+            };
+            let result = match apply_timeout(request, self.timeout).await {
+                Ok(result) => result.map_err(|source| TissueError::Transport {
+                    url: self.webhook_url("***"),
+                    source: hide_id(source, &self.id),
+                }),
+                Err(timeout) => Err(timeout),
+            };
+
+            let (retryable, retry_after) = match &result {
+                Ok(response) => match response["status"].as_u64() {
+                    Some(429) => (true, response["retry_after"].as_u64()),
+                    Some(status) => (status >= 500, None),
+                    None => (false, None),
+                },
+                Err(_) => (true, None),
+            };
+            if !retryable || attempt >= self.retries {
+                return result;
+            }
+            #[cfg(feature = "log")]
+            log::warn!(
+                "Retrying checkin, attempt {} of {}",
+                attempt + 1,
+                self.retries
+            );
+
+            Delay::new(self.retry_delay(attempt, retry_after.map(Duration::from_secs))).await;
+            attempt += 1;
+        }
+    }
+}
+
+/// Increment of SplitMix64 state.
+const SPLITMIX_GAMMA: u64 = 0x9e37_79b9_7f4a_7c15;
+
+/// Replaces the webhook ID in the URL path of a requester error message with `***`,
+/// keeping the original error as its source.
+fn hide_id(
+    error: Box<dyn Error + Send + Sync + 'static>,
+    id: &str,
+) -> Box<dyn Error + Send + Sync + 'static> {
+    if id.is_empty() {
+        return error;
+    }
+    let secret = format!("/api/webhooks/checkin/{}", id);
+    RedactedError::wrap(error, secret, "/api/webhooks/checkin/***".into())
+}
+
+/// Checks that the authority is a host name, IPv4 or bracketed IPv6 address, with optional port.
+/// User information like `user@` is rejected as well.
+fn is_valid_authority(authority: &str) -> bool {
+    let valid_host = |c: char| c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '_');
+    let (valid, port) = match authority.strip_prefix('[') {
+        Some(rest) => match rest.split_once(']') {
+            Some((address, port)) => (address.parse::<Ipv6Addr>().is_ok(), port),
+            None => return false,
+        },
+        None => {
+            let (host, port) = authority.split_at(authority.find(':').unwrap_or(authority.len()));
+            (!host.is_empty() && host.chars().all(valid_host), port)
+        }
+    };
+    let valid_port = match port.strip_prefix(':') {
+        Some(port) => port.parse::<u16>().is_ok(),
+        None => port.is_empty(),
+    };
+    valid && valid_port
+}
+
+/// Checks that the path has only visible ASCII characters without query, fragment and `\`.
+fn is_valid_path(path: &str) -> bool {
+    path.chars()
+        .all(|c| c.is_ascii_graphic() && !matches!(c, '?' | '#' | '\\'))
 }
 
-fn parse_response(
-    value: &Value,
-) -> Result<CheckinResponse, Box<dyn Error + Send + Sync + 'static>> {
-    let status_code = value["status"].as_u64().expect("Status code should exist");
+/// Checks that the webhook ID is non-empty and consists of alphanumerics, `-` and `_`.
+fn check_id(id: &str) -> Result<(), TissueError> {
+    let valid = |c: char| c.is_ascii_alphanumeric() || c == '-' || c == '_';
+    if id.is_empty() || !id.chars().all(valid) {
+        return Err(TissueError::InvalidId(id.into()));
+    }
+    Ok(())
+}
+
+/// Parses `violations` in either an array of messages or an object keyed by field names.
+/// Returns `None` if it is neither.
+fn parse_violations(value: &Value) -> Option<Vec<Violation>> {
+    let mut violations = vec![];
+    match value {
+        Value::Array(messages) => {
+            for message in messages.iter().filter_map(Value::as_str) {
+                violations.push(Violation {
+                    field: None,
+                    message: message.into(),
+                });
+            }
+        }
+        Value::Object(fields) => {
+            for (field, messages) in fields {
+                let messages = match messages {
+                    Value::Array(messages) => messages.as_slice(),
+                    message => slice::from_ref(message),
+                };
+                for message in messages.iter().filter_map(Value::as_str) {
+                    violations.push(Violation {
+                        field: Some(field.clone()),
+                        message: message.into(),
+                    });
+                }
+            }
+        }
+        _ => return None,
+    }
+
+    Some(violations)
+}
+
+/// `IncomingEndpoint` with a requester chosen at runtime.
+pub type BoxedIncomingEndpoint = IncomingEndpoint<Box<dyn TissueRequester>>;
+
+impl BoxedIncomingEndpoint {
+    /// Creates a new endpoint with ID and boxed requester.
+    pub fn new_boxed(id: &str, requester: Box<dyn TissueRequester>) -> BoxedIncomingEndpoint {
+        IncomingEndpoint::new(id, requester)
+    }
+}
+
+/// Awaits the future, failing with `TissueError::Timeout` if it does not complete in time.
+async fn apply_timeout<F: Future + Unpin>(
+    future: F,
+    timeout: Option<Duration>,
+) -> Result<F::Output, TissueError> {
+    let timeout = match timeout {
+        Some(timeout) => timeout,
+        None => return Ok(future.await),
+    };
+
+    match select(future, Delay::new(timeout)).await {
+        Either::Left((output, _)) => Ok(output),
+        Either::Right(_) => Err(TissueError::Timeout(timeout)),
+    }
+}
+
+/// Parses a checkin response, recording its kind in the current span with `tracing` feature.
+pub(crate) fn parse_response(value: Value) -> Result<CheckinResponse, TissueError> {
+    let response = parse_checkin_response(value);
+    #[cfg(feature = "tracing")]
+    if let Ok(response) = &response {
+        tracing::Span::current().record("kind", response.kind());
+    }
+    response
+}
+
+/// Parses a checkin response, moving subtrees out of `value` instead of cloning.
+fn parse_checkin_response(mut value: Value) -> Result<CheckinResponse, TissueError> {
+    let status_code = match value["status"].as_u64() {
+        Some(status_code) => status_code,
+        None => return Err(TissueError::MalformedResponse(value)),
+    };
     match status_code {
-        200 => {
-            let received_checkin = from_value(value["checkin"].clone())?;
+        200 | 201 => {
+            // Webhook wraps the checkin, while API returns it as is
+            let checkin = match value.get_mut("checkin") {
+                Some(checkin) => checkin.take(),
+                None => value,
+            };
+            let received_checkin = from_value(checkin)?;
             Ok(CheckinResponse::Success(received_checkin))
         }
         404 | 422 => {
             let error_object = &value["error"];
-            if error_object["violations"].is_array() {
+            if let Some(violations) = parse_violations(&error_object["violations"]) {
                 // Validation error
-                let violations = from_value(error_object["violations"].clone())?;
                 Ok(CheckinResponse::ValidationError(violations))
             } else {
                 // Other error
                 let message = error_object["message"].as_str().unwrap_or("");
-                Ok(CheckinResponse::OtherError(message.into()))
+                Ok(CheckinResponse::OtherError {
+                    status: status_code as u16,
+                    message: message.into(),
+                })
+            }
+        }
+        401 | 403 => {
+            let message = value["error"]["message"]
+                .as_str()
+                .or_else(|| value["message"].as_str())
+                .unwrap_or("");
+            Ok(CheckinResponse::Unauthorized {
+                status: status_code as u16,
+                message: message.into(),
+            })
+        }
+        429 => {
+            let retry_after = value["retry_after"].as_u64().map(Duration::from_secs);
+            Ok(CheckinResponse::RateLimited { retry_after })
+        }
+        otherwise => Err(TissueError::UnexpectedStatus {
+            status: otherwise,
+            body: value,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::requester::{success_response, MockRequester};
+
+    use futures::executor::block_on;
+    use serde_json::json;
+
+    #[test]
+    fn transport_error_hides_webhook_id() {
+        let requester = MockRequester::new();
+        requester.push_error(
+            "error sending request for url (https://shikorism.net/api/webhooks/checkin/SECRETID)",
+        );
+        let endpoint = IncomingEndpoint::new("SECRETID", requester);
+
+        let error = block_on(endpoint.send_checkin(&Checkin::builder().build())).unwrap_err();
+        match &error {
+            TissueError::Transport { url, .. } => {
+                assert_eq!(url, "https://shikorism.net/api/webhooks/checkin/***")
+            }
+            error => panic!("Unexpected error: {:?}", error),
+        }
+        assert!(!error.to_string().contains("SECRETID"));
+    }
+
+    #[test]
+    fn jitter_stays_within_backoff() {
+        let backoff = Duration::from_millis(100);
+        let endpoint = IncomingEndpoint::new("id", MockRequester::new())
+            .with_retries(8, backoff)
+            .with_jitter(42);
+        let delays: Vec<_> = (0..8)
+            .map(|attempt| endpoint.backoff_delay(attempt))
+            .collect();
+        for (attempt, delay) in delays.iter().enumerate() {
+            assert!(*delay <= backoff * 2u32.pow(attempt as u32), "{:?}", delay);
+        }
+        assert!(delays
+            .iter()
+            .enumerate()
+            .any(|(attempt, delay)| *delay < backoff * 2u32.pow(attempt as u32)));
+
+        let same_seed = IncomingEndpoint::new("id", MockRequester::new())
+            .with_retries(8, backoff)
+            .with_jitter(42);
+        let same_delays: Vec<_> = (0..8)
+            .map(|attempt| same_seed.backoff_delay(attempt))
+            .collect();
+        assert_eq!(delays, same_delays);
+    }
+
+    #[test]
+    fn retry_after_is_floor_of_delay() {
+        let endpoint = IncomingEndpoint::new("id", MockRequester::new())
+            .with_retries(1, Duration::from_secs(1))
+            .with_jitter(42);
+        assert_eq!(
+            endpoint.retry_delay(0, Some(Duration::from_secs(5))),
+            Duration::from_secs(5)
+        );
+        assert!(endpoint.retry_delay(0, Some(Duration::from_secs(0))) <= Duration::from_secs(1));
+    }
+
+    #[test]
+    fn rate_limited_is_retried_once_per_attempt() {
+        let requester = MockRequester::with_responses(vec![
+            json!({ "status": 429, "retry_after": 0 }),
+            json!({ "status": 429, "retry_after": 0 }),
+            json!({ "status": 429, "retry_after": 0 }),
+            json!({ "status": 429, "retry_after": 0 }),
+        ]);
+        let endpoint =
+            IncomingEndpoint::new("id", requester.clone()).with_retries(2, Duration::ZERO);
+
+        let results = block_on(endpoint.send_checkins(&[Checkin::builder().build()], 1));
+        assert!(matches!(
+            results[0],
+            Ok(CheckinResponse::RateLimited { .. })
+        ));
+        assert_eq!(requester.requests().len(), 3);
+    }
+
+    #[test]
+    fn send_checkin_retries_rate_limited() {
+        let requester = MockRequester::with_responses(vec![
+            json!({ "status": 429, "retry_after": 0 }),
+            success_response(),
+        ]);
+        let endpoint =
+            IncomingEndpoint::new("id", requester.clone()).with_retries(1, Duration::ZERO);
+
+        let response = block_on(endpoint.send_checkin(&Checkin::builder().build())).unwrap();
+        assert!(response.is_success());
+        assert_eq!(requester.requests().len(), 2);
+    }
+
+    #[test]
+    fn retries_until_success() {
+        let requester = MockRequester::new();
+        requester.push_error("connection reset");
+        requester.push_response(json!({ "status": 503 }));
+        requester.push_response(success_response());
+        let endpoint =
+            IncomingEndpoint::new("id", requester.clone()).with_retries(3, Duration::ZERO);
+
+        let response = block_on(endpoint.send_checkin(&Checkin::builder().build())).unwrap();
+        assert!(response.is_success());
+        assert_eq!(requester.requests().len(), 3);
+    }
+
+    #[test]
+    fn validation_error_is_not_retried() {
+        let requester = MockRequester::with_responses(vec![
+            json!({
+                "status": 422,
+                "error": { "message": "Validation failed", "violations": ["note is too long"] },
+            }),
+            success_response(),
+        ]);
+        let endpoint =
+            IncomingEndpoint::new("id", requester.clone()).with_retries(3, Duration::ZERO);
+
+        let response = block_on(endpoint.send_checkin(&Checkin::builder().build())).unwrap();
+        assert!(matches!(response, CheckinResponse::ValidationError(_)));
+        assert_eq!(requester.requests().len(), 1);
+    }
+
+    #[test]
+    fn gives_up_after_retries() {
+        let requester = MockRequester::new();
+        for _ in 0..3 {
+            requester.push_error("connection reset");
+        }
+        let endpoint =
+            IncomingEndpoint::new("id", requester.clone()).with_retries(2, Duration::ZERO);
+
+        let error = block_on(endpoint.send_checkin(&Checkin::builder().build())).unwrap_err();
+        assert!(matches!(error, TissueError::Transport { .. }));
+        assert_eq!(requester.requests().len(), 3);
+    }
+
+    #[test]
+    fn id_is_validated() {
+        let endpoint = IncomingEndpoint::try_new("abc-DEF_123", MockRequester::new()).unwrap();
+        assert_eq!(
+            endpoint.target_url(),
+            "https://shikorism.net/api/webhooks/checkin/abc-DEF_123"
+        );
+
+        for id in &[
+            "",
+            "a/b",
+            "a/b?c",
+            "https://shikorism.net/api/webhooks/checkin/abc",
+        ] {
+            assert!(matches!(
+                IncomingEndpoint::try_new(id, MockRequester::new()),
+                Err(TissueError::InvalidId(_))
+            ));
+            assert!(matches!(
+                IncomingEndpoint::try_with_domain("x", id, MockRequester::new()),
+                Err(TissueError::InvalidId(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn base_url_is_validated() {
+        let endpoint =
+            IncomingEndpoint::with_base_url("http://localhost:8000", "id", MockRequester::new())
+                .unwrap();
+        assert_eq!(
+            endpoint.target_url(),
+            "http://localhost:8000/api/webhooks/checkin/id"
+        );
+
+        let endpoint = IncomingEndpoint::with_base_url(
+            "https://[::1]:8443/tissue/",
+            "id",
+            MockRequester::new(),
+        )
+        .unwrap();
+        assert_eq!(
+            endpoint.target_url(),
+            "https://[::1]:8443/tissue/api/webhooks/checkin/id"
+        );
+
+        for base in &[
+            "localhost:8000",
+            "ftp://localhost",
+            "http://",
+            "https://host?x#y/",
+            "https://host/x?y",
+            "https://user@host",
+            "https://host:port",
+            "https://host:99999",
+            "https://[::1",
+            "https://ho st",
+        ] {
+            assert!(
+                matches!(
+                    IncomingEndpoint::with_base_url(base, "id", MockRequester::new()),
+                    Err(TissueError::InvalidUrl(_))
+                ),
+                "{}",
+                base
+            );
+        }
+    }
+
+    #[test]
+    fn parses_non_json_body() {
+        let body = json!({ "status": 502, "body": "<html>Bad Gateway</html>" });
+        match parse_response(body.clone()) {
+            Err(TissueError::UnexpectedStatus {
+                status: 502,
+                body: returned,
+            }) => {
+                assert_eq!(returned, body)
+            }
+            result => panic!("Unexpected result: {:?}", result),
+        }
+
+        let body = json!({ "body": "<html>Bad Gateway</html>" });
+        assert!(matches!(
+            parse_response(body),
+            Err(TissueError::MalformedResponse(_))
+        ));
+    }
+
+    #[cfg(feature = "log")]
+    #[test]
+    fn logs_hide_note_and_link() {
+        use log::{Log, Metadata, Record};
+        use std::sync::Mutex;
+
+        static MESSAGES: Mutex<Vec<String>> = Mutex::new(Vec::new());
+        struct Capture;
+        impl Log for Capture {
+            fn enabled(&self, _: &Metadata<'_>) -> bool {
+                true
+            }
+            fn log(&self, record: &Record<'_>) {
+                MESSAGES.lock().unwrap().push(record.args().to_string());
+            }
+            fn flush(&self) {}
+        }
+        log::set_logger(&Capture).unwrap();
+        log::set_max_level(log::LevelFilter::Debug);
+
+        let requester = MockRequester::with_responses(vec![json!({
+            "status": 500,
+            "echo": { "note": "SECRETNOTE", "link": "https://example.com/SECRETLINK" },
+        })]);
+        let endpoint = IncomingEndpoint::new("SECRETID", requester);
+        let mut builder = Checkin::builder();
+        builder.note("SECRETNOTE").unwrap();
+        builder.link("https://example.com/SECRETLINK").unwrap();
+        assert!(block_on(endpoint.send_checkin(&builder.build())).is_err());
+
+        let messages = MESSAGES.lock().unwrap();
+        assert!(messages
+            .iter()
+            .any(|message| message.contains("UnexpectedStatus")));
+        for message in messages.iter() {
+            assert!(!message.contains("SECRET"), "{}", message);
+        }
+    }
+
+    #[test]
+    fn headers_are_sent_on_every_attempt() {
+        let requester = MockRequester::new();
+        requester.push_error("connection reset");
+        requester.push_response(success_response());
+        let endpoint = IncomingEndpoint::new("id", requester.clone())
+            .with_header("X-Client", "test")
+            .with_retries(1, Duration::ZERO);
+
+        let mut extra = HashMap::new();
+        extra.insert("X-Request".into(), "1".into());
+        let checkin = Checkin::builder().build();
+        block_on(endpoint.send_checkin_with_headers(&checkin, extra)).unwrap();
+
+        let requests = requester.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0], requests[1]);
+        assert_eq!(requests[1].headers()["X-Client"], "test");
+        assert_eq!(requests[1].headers()["X-Request"], "1");
+    }
+
+    /// Requester that responds after `delay`.
+    struct DelayedRequester {
+        delay: Duration,
+        inner: MockRequester,
+    }
+
+    #[async_trait::async_trait]
+    impl TissueRequester for DelayedRequester {
+        async fn get(
+            &self,
+            url: String,
+            headers: HashMap<String, String>,
+        ) -> Result<Value, Box<dyn Error + Send + Sync>> {
+            Delay::new(self.delay).await;
+            self.inner.get(url, headers).await
+        }
+
+        async fn post(
+            &self,
+            url: String,
+            headers: HashMap<String, String>,
+            body: Value,
+        ) -> Result<Value, Box<dyn Error + Send + Sync>> {
+            Delay::new(self.delay).await;
+            self.inner.post(url, headers, body).await
+        }
+
+        async fn put(
+            &self,
+            url: String,
+            headers: HashMap<String, String>,
+            body: Value,
+        ) -> Result<Value, Box<dyn Error + Send + Sync>> {
+            Delay::new(self.delay).await;
+            self.inner.put(url, headers, body).await
+        }
+
+        async fn delete(
+            &self,
+            url: String,
+            headers: HashMap<String, String>,
+        ) -> Result<Value, Box<dyn Error + Send + Sync>> {
+            Delay::new(self.delay).await;
+            self.inner.delete(url, headers).await
+        }
+    }
+
+    #[test]
+    fn slow_response_times_out() {
+        let requester = DelayedRequester {
+            delay: Duration::from_secs(10),
+            inner: MockRequester::with_responses(vec![success_response()]),
+        };
+        let timeout = Duration::from_millis(10);
+        let endpoint = IncomingEndpoint::new("id", requester).with_timeout(timeout);
+
+        let error = block_on(endpoint.send_checkin(&Checkin::builder().build())).unwrap_err();
+        assert!(matches!(error, TissueError::Timeout(t) if t == timeout));
+    }
+
+    #[test]
+    fn fast_response_does_not_time_out() {
+        let requester = DelayedRequester {
+            delay: Duration::from_millis(1),
+            inner: MockRequester::with_responses(vec![success_response()]),
+        };
+        let endpoint = IncomingEndpoint::new("id", requester).with_timeout(Duration::from_secs(10));
+
+        let response = block_on(endpoint.send_checkin(&Checkin::builder().build())).unwrap();
+        assert!(response.is_success());
+    }
+
+    #[test]
+    fn boxed_endpoints_hold_different_requesters() {
+        let mock = MockRequester::with_responses(vec![success_response()]);
+        let delayed = DelayedRequester {
+            delay: Duration::from_millis(1),
+            inner: MockRequester::with_responses(vec![success_response()]),
+        };
+        let endpoints: Vec<BoxedIncomingEndpoint> = vec![
+            IncomingEndpoint::new_boxed("first", Box::new(mock.clone())),
+            IncomingEndpoint::new_boxed("second", Box::new(delayed)),
+        ];
+
+        let checkin = Checkin::builder().build();
+        for endpoint in &endpoints {
+            assert!(block_on(endpoint.send_checkin(&checkin))
+                .unwrap()
+                .is_success());
+        }
+        assert_eq!(mock.requests().len(), 1);
+    }
+
+    #[test]
+    fn parses_rate_limited() {
+        let response = parse_response(json!({ "status": 429, "retry_after": 30 })).unwrap();
+        assert_eq!(
+            response,
+            CheckinResponse::RateLimited {
+                retry_after: Some(Duration::from_secs(30))
+            }
+        );
+        let response = parse_response(json!({ "status": 429 })).unwrap();
+        assert_eq!(response, CheckinResponse::RateLimited { retry_after: None });
+    }
+
+    #[test]
+    fn parses_unauthorized() {
+        let response = parse_response(json!({
+            "status": 401,
+            "error": { "message": "Unauthenticated." },
+        }))
+        .unwrap();
+        assert_eq!(
+            response,
+            CheckinResponse::Unauthorized {
+                status: 401,
+                message: "Unauthenticated.".into(),
+            }
+        );
+
+        let response = parse_response(json!({ "status": 403, "message": "Forbidden" })).unwrap();
+        assert_eq!(
+            response,
+            CheckinResponse::Unauthorized {
+                status: 403,
+                message: "Forbidden".into(),
+            }
+        );
+    }
+
+    #[test]
+    fn parses_other_error() {
+        let response = parse_response(json!({
+            "status": 404,
+            "error": { "message": "Webhook not found" },
+        }))
+        .unwrap();
+        assert_eq!(
+            response,
+            CheckinResponse::OtherError {
+                status: 404,
+                message: "Webhook not found".into(),
             }
+        );
+    }
+
+    #[test]
+    fn parses_violations() {
+        let response = parse_response(json!({
+            "status": 422,
+            "error": { "message": "Validation failed", "violations": ["note is too long"] },
+        }))
+        .unwrap();
+        match response {
+            CheckinResponse::ValidationError(violations) => {
+                assert_eq!(violations.len(), 1);
+                assert_eq!(violations[0].field(), None);
+                assert_eq!(violations[0].message(), "note is too long");
+            }
+            response => panic!("Unexpected response: {:?}", response),
+        }
+
+        let response = parse_response(json!({
+            "status": 422,
+            "error": {
+                "message": "Validation failed",
+                "violations": {
+                    "note": ["note is too long", "note is invalid"],
+                    "link": "link is invalid",
+                },
+            },
+        }))
+        .unwrap();
+        match response {
+            CheckinResponse::ValidationError(violations) => {
+                let mut pairs: Vec<_> = violations
+                    .iter()
+                    .map(|violation| (violation.field().unwrap(), violation.message()))
+                    .collect();
+                pairs.sort();
+                assert_eq!(
+                    pairs,
+                    vec![
+                        ("link", "link is invalid"),
+                        ("note", "note is invalid"),
+                        ("note", "note is too long"),
+                    ]
+                );
+            }
+            response => panic!("Unexpected response: {:?}", response),
+        }
+    }
+
+    #[test]
+    fn parses_null_note_and_link() {
+        let mut body = success_response();
+        body["checkin"]["note"] = Value::Null;
+        body["checkin"]["link"] = Value::Null;
+        let received = parse_response(body).unwrap().into_received().unwrap();
+        assert_eq!(received.note(), None);
+        assert_eq!(received.link(), None);
+
+        let mut body = success_response();
+        let checkin = body["checkin"].as_object_mut().unwrap();
+        checkin.remove("note");
+        checkin.remove("link");
+        assert!(parse_response(body).unwrap().is_success());
+    }
+
+    #[test]
+    fn parses_unexpected_status() {
+        let body = json!({ "status": 500, "message": "Server Error" });
+        match parse_response(body.clone()) {
+            Err(TissueError::UnexpectedStatus {
+                status: 500,
+                body: returned,
+            }) => assert_eq!(returned, body),
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+
+    #[test]
+    fn endpoint_source_is_default() {
+        let requester = MockRequester::with_responses(vec![success_response(), success_response()]);
+        let endpoint = IncomingEndpoint::new("id", requester.clone()).with_source("my-app");
+
+        block_on(endpoint.send_checkin(&Checkin::builder().build())).unwrap();
+        let mut builder = Checkin::builder();
+        builder.source("own").unwrap();
+        block_on(endpoint.send_checkin(&builder.build())).unwrap();
+
+        let requests = requester.requests();
+        assert_eq!(requests[0].body().unwrap()["source"], "my-app");
+        assert_eq!(requests[1].body().unwrap()["source"], "own");
+    }
+
+    #[test]
+    fn domain_and_id_read_back() {
+        let endpoint =
+            IncomingEndpoint::with_domain("tissue.example.com", "id", MockRequester::new());
+        assert_eq!(endpoint.domain(), "tissue.example.com");
+        assert_eq!(endpoint.id(), "id");
+    }
+
+    #[test]
+    fn requester_is_recovered() {
+        let requester = MockRequester::new();
+        requester.push_response(success_response());
+        let mut endpoint = IncomingEndpoint::new("id", requester);
+        endpoint.requester_mut().push_response(success_response());
+
+        let requester = endpoint.into_requester();
+        assert!(requester.requests().is_empty());
+        let response = block_on(requester.post(String::new(), HashMap::new(), Value::Null));
+        assert_eq!(response.unwrap(), success_response());
+    }
+
+    #[test]
+    fn sends_several_checkins() {
+        let requester = MockRequester::with_responses((0..5).map(|_| success_response()));
+        let endpoint = IncomingEndpoint::new("id", requester.clone());
+        let checkins: Vec<_> = (0..5)
+            .map(|i| {
+                let mut builder = Checkin::builder();
+                builder.note(&i.to_string()).unwrap();
+                builder.build()
+            })
+            .collect();
+
+        let results = block_on(endpoint.send_checkins(&checkins, 2));
+        assert_eq!(results.len(), 5);
+        assert!(results
+            .iter()
+            .all(|result| matches!(result, Ok(response) if response.is_success())));
+        assert_eq!(requester.requests().len(), 5);
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn send_runs_in_span() {
+        use std::{
+            fmt::Debug,
+            sync::{
+                atomic::{AtomicU64, Ordering},
+                Arc, Mutex,
+            },
+        };
+        use tracing::{
+            field::{Field, Visit},
+            span::{Attributes, Id, Record},
+            Event, Metadata, Subscriber,
+        };
+
+        /// Records span names and fields as strings, tracking the entered spans.
+        #[derive(Default)]
+        struct Capture {
+            next_id: AtomicU64,
+            records: Arc<Mutex<Vec<String>>>,
+            spans: Mutex<HashMap<u64, &'static Metadata<'static>>>,
+            entered: Mutex<Vec<Id>>,
+        }
+
+        struct Recorder<'a>(&'a Mutex<Vec<String>>);
+
+        impl Visit for Recorder<'_> {
+            fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+                let record = format!("{}={:?}", field.name(), value);
+                self.0.lock().unwrap().push(record);
+            }
+        }
+
+        impl Subscriber for Capture {
+            fn enabled(&self, _: &Metadata<'_>) -> bool {
+                true
+            }
+            fn new_span(&self, span: &Attributes<'_>) -> Id {
+                self.records
+                    .lock()
+                    .unwrap()
+                    .push(span.metadata().name().into());
+                span.record(&mut Recorder(&self.records));
+                let id = self.next_id.fetch_add(1, Ordering::Relaxed) + 1;
+                self.spans.lock().unwrap().insert(id, span.metadata());
+                Id::from_u64(id)
+            }
+            fn record(&self, _: &Id, values: &Record<'_>) {
+                values.record(&mut Recorder(&self.records));
+            }
+            fn record_follows_from(&self, _: &Id, _: &Id) {}
+            fn event(&self, event: &Event<'_>) {
+                event.record(&mut Recorder(&self.records));
+            }
+            fn enter(&self, id: &Id) {
+                self.entered.lock().unwrap().push(id.clone());
+            }
+            fn exit(&self, _: &Id) {
+                self.entered.lock().unwrap().pop();
+            }
+            fn current_span(&self) -> tracing_core::span::Current {
+                match self.entered.lock().unwrap().last() {
+                    Some(id) => tracing_core::span::Current::new(
+                        id.clone(),
+                        self.spans.lock().unwrap()[&id.into_u64()],
+                    ),
+                    None => tracing_core::span::Current::none(),
+                }
+            }
+        }
+
+        let capture = Capture::default();
+        let records = Arc::clone(&capture.records);
+        let requester = MockRequester::with_responses(vec![success_response()]);
+        let endpoint = IncomingEndpoint::new("SECRETID", requester);
+        let mut builder = Checkin::builder();
+        builder.note("SECRETNOTE").unwrap();
+        let checkin = builder.build();
+        tracing::subscriber::with_default(capture, || {
+            block_on(endpoint.send_checkin(&checkin)).unwrap();
+        });
+
+        let records = records.lock().unwrap();
+        assert!(records.contains(&"send_checkin".to_string()));
+        assert!(records.contains(&"domain=shikorism.net".to_string()));
+        assert!(records.contains(&"kind=\"Success\"".to_string()));
+        assert!(records.iter().all(|record| !record.contains("SECRET")));
+    }
+
+    #[test]
+    fn base_path_is_inserted_before_api() {
+        for base_path in &["tissue", "/tissue/", "//tissue"] {
+            let endpoint = IncomingEndpoint::with_domain("example.com", "id", MockRequester::new())
+                .with_base_path(base_path);
+            assert_eq!(
+                endpoint.target_url(),
+                "https://example.com/tissue/api/webhooks/checkin/id"
+            );
+        }
+
+        let endpoint = IncomingEndpoint::new("id", MockRequester::new()).with_base_path("/");
+        assert_eq!(
+            endpoint.target_url(),
+            "https://shikorism.net/api/webhooks/checkin/id"
+        );
+    }
+
+    #[test]
+    fn parses_wrapped_and_bare_checkin_alike() {
+        let wrapped = parse_response(success_response()).unwrap();
+        let mut bare = success_response()["checkin"].clone();
+        bare["status"] = 201.into();
+        assert_eq!(parse_response(bare).unwrap(), wrapped);
+
+        let received = wrapped.into_received().unwrap();
+        assert_eq!(received.id(), 1);
+        assert_eq!(received.source_kind(), SourceKind::Webhook);
+        assert_eq!(received.note().map(String::as_str), Some(""));
+    }
+
+    #[test]
+    fn checkin_ref_borrows_from_input() {
+        let input = r#"{
+            "id": 1,
+            "checked_in_at": "2021-01-01T00:00:00+09:00",
+            "note": "borrowed note",
+            "link": "https://example.com/\u00e9",
+            "tags": ["anime"],
+            "source": "api",
+            "is_private": false,
+            "is_too_sensitive": false
+        }"#;
+        let checkin: ReceivedCheckinRef<'_> = serde_json::from_str(input).unwrap();
+        assert!(matches!(
+            checkin.note,
+            Some(CowStr(Cow::Borrowed("borrowed note")))
+        ));
+        // Escaped strings cannot be borrowed
+        assert!(matches!(checkin.link, Some(CowStr(Cow::Owned(_)))));
+        assert_eq!(checkin.source_kind(), SourceKind::Api);
+
+        let owned = checkin.into_owned();
+        assert_eq!(owned.note().map(String::as_str), Some("borrowed note"));
+        assert_eq!(
+            owned.link().map(String::as_str),
+            Some("https://example.com/é")
+        );
+        assert_eq!(owned.tags().collect::<Vec<_>>(), ["anime"]);
+    }
+
+    #[test]
+    fn idempotency_key_is_reused_for_retries() {
+        let requester = MockRequester::with_responses(vec![
+            json!({ "status": 503 }),
+            json!({ "status": 502 }),
+            success_response(),
+        ]);
+        let endpoint = IncomingEndpoint::new("id", requester.clone())
+            .with_retries(2, Duration::ZERO)
+            .with_idempotency_keys();
+
+        block_on(endpoint.send_checkin(&Checkin::builder().build())).unwrap();
+        let keys: Vec<_> = requester
+            .requests()
+            .iter()
+            .map(|request| request.headers()["Idempotency-Key"].clone())
+            .collect();
+        assert_eq!(keys.len(), 3);
+        assert_eq!(keys[0].len(), 32);
+        assert!(keys.iter().all(|key| *key == keys[0]));
+
+        // Another checkin gets another key
+        requester.push_response(success_response());
+        block_on(endpoint.send_checkin(&Checkin::builder().build())).unwrap();
+        assert_ne!(
+            requester.requests()[3].headers()["Idempotency-Key"],
+            keys[0]
+        );
+    }
+
+    #[test]
+    fn given_idempotency_key_is_sent() {
+        let requester =
+            MockRequester::with_responses(vec![json!({ "status": 500 }), success_response()]);
+        let endpoint =
+            IncomingEndpoint::new("id", requester.clone()).with_retries(1, Duration::ZERO);
+
+        let checkin = Checkin::builder().build();
+        block_on(endpoint.send_checkin_with_idempotency_key(&checkin, "my-key")).unwrap();
+        for request in requester.requests() {
+            assert_eq!(request.headers()["Idempotency-Key"], "my-key");
         }
-        otherwise => {
-            Err(format!("Unknown status code: {}, response: {}", otherwise, value).into())
+    }
+
+    #[test]
+    fn previewed_body_is_sent() {
+        let requester = MockRequester::with_responses(vec![success_response()]);
+        let endpoint = IncomingEndpoint::new("id", requester.clone()).with_source("my-app");
+        let mut builder = Checkin::builder();
+        builder.note("Hello").unwrap().tags(["anime"]).unwrap();
+        let checkin = builder.build();
+
+        let body = endpoint.checkin_body(&checkin).unwrap();
+        assert_eq!(body["source"], "my-app");
+        block_on(endpoint.send_checkin(&checkin)).unwrap();
+        assert_eq!(requester.requests()[0].body(), Some(&body));
+    }
+
+    #[test]
+    fn dry_run_sends_nothing() {
+        let requester = MockRequester::new();
+        let endpoint = IncomingEndpoint::new("id", requester.clone());
+        let mut builder = Checkin::builder();
+        builder.note("Hello").unwrap();
+        let checkin = builder.build();
+
+        let (url, body) = endpoint.send_checkin_dry_run(&checkin).unwrap();
+        assert_eq!(url, "https://shikorism.net/api/webhooks/checkin/id");
+        assert_eq!(body, endpoint.checkin_body(&checkin).unwrap());
+        assert!(requester.requests().is_empty());
+    }
+
+    #[test]
+    fn content_type_is_sent() {
+        let requester = MockRequester::with_responses(vec![success_response()]);
+        let endpoint = IncomingEndpoint::new("id", requester.clone())
+            .with_content_type("application/json; charset=utf-8");
+
+        block_on(endpoint.send_checkin(&Checkin::builder().build())).unwrap();
+        assert_eq!(
+            requester.requests()[0].headers()["Content-Type"],
+            "application/json; charset=utf-8"
+        );
+    }
+
+    #[test]
+    fn received_checkins_sort_by_timestamp_then_id() {
+        let received = |id: usize, checked_in_at: &str| -> ReceivedCheckin {
+            from_value(json!({
+                "id": id,
+                "checked_in_at": checked_in_at,
+                "tags": [],
+                "source": "web",
+                "is_private": false,
+                "is_too_sensitive": false,
+            }))
+            .unwrap()
+        };
+        let mut checkins = [
+            received(1, "2021-03-01T00:00:00+09:00"),
+            received(4, "2021-01-01T00:00:00+09:00"),
+            received(3, "2021-02-01T00:00:00+09:00"),
+            // Same instant as ID 3 in another offset
+            received(2, "2021-01-31T15:00:00+00:00"),
+        ];
+        checkins.sort();
+        let ids: Vec<_> = checkins.iter().map(ReceivedCheckin::id).collect();
+        assert_eq!(ids, [4, 2, 3, 1]);
+    }
+
+    #[test]
+    fn endpoint_from_webhook_url() {
+        let endpoint = IncomingEndpoint::from_webhook_url(
+            "https://shikorism.net/api/webhooks/checkin/AbC-123",
+            MockRequester::new(),
+        )
+        .unwrap();
+        assert_eq!(endpoint.domain(), "shikorism.net");
+        assert_eq!(endpoint.id(), "AbC-123");
+        assert_eq!(
+            endpoint.target_url(),
+            "https://shikorism.net/api/webhooks/checkin/AbC-123"
+        );
+
+        let endpoint = IncomingEndpoint::from_webhook_url(
+            " http://tissue.example.com:8080/tissue/api/webhooks/checkin/id/ ",
+            MockRequester::new(),
+        )
+        .unwrap();
+        assert_eq!(endpoint.domain(), "tissue.example.com:8080");
+        assert_eq!(endpoint.id(), "id");
+        assert_eq!(
+            endpoint.target_url(),
+            "http://tissue.example.com:8080/tissue/api/webhooks/checkin/id"
+        );
+
+        assert!(matches!(
+            IncomingEndpoint::from_webhook_url("https://shikorism.net/", MockRequester::new()),
+            Err(TissueError::InvalidUrl(_))
+        ));
+    }
+
+    #[test]
+    fn by_id_dedups_refetched_checkin() {
+        use std::collections::HashSet;
+
+        let mut first = success_response()["checkin"].clone();
+        first["note"] = "before".into();
+        let mut second = first.clone();
+        second["note"] = "after".into();
+        let first: ReceivedCheckin = from_value(first).unwrap();
+        let second: ReceivedCheckin = from_value(second).unwrap();
+        assert_ne!(first, second);
+
+        let mut set = HashSet::new();
+        assert!(set.insert(ById(first)));
+        assert!(!set.insert(ById(second)));
+        assert_eq!(set.len(), 1);
+        let kept = set.into_iter().next().unwrap().into_inner();
+        assert_eq!(kept.note().map(String::as_str), Some("before"));
+    }
+
+    #[test]
+    fn response_helpers_for_each_variant() {
+        let success = parse_response(success_response()).unwrap();
+        assert!(success.is_success());
+        assert_eq!(success.as_received().map(ReceivedCheckin::id), Some(1));
+        assert_eq!(success.into_received().map(|checkin| checkin.id()), Some(1));
+
+        let failures = vec![
+            CheckinResponse::ValidationError(vec![]),
+            CheckinResponse::OtherError {
+                status: 404,
+                message: "Not Found".into(),
+            },
+            CheckinResponse::Unauthorized {
+                status: 401,
+                message: "Unauthorized".into(),
+            },
+            CheckinResponse::RateLimited { retry_after: None },
+        ];
+        for failure in failures {
+            assert!(!failure.is_success());
+            assert!(failure.as_received().is_none());
+            assert!(failure.into_received().is_none());
+        }
+    }
+
+    #[test]
+    fn query_params_are_encoded_on_url() {
+        let requester = MockRequester::with_responses(vec![success_response()]);
+        let endpoint = IncomingEndpoint::new("id", requester.clone())
+            .with_query_param("route", "a b&c")
+            .with_query_param("route", "日本")
+            .with_query_param("x=y", "1");
+
+        block_on(endpoint.send_checkin(&Checkin::builder().build())).unwrap();
+        assert_eq!(
+            requester.requests()[0].url(),
+            "https://shikorism.net/api/webhooks/checkin/id\
+             ?route=a%20b%26c&route=%E6%97%A5%E6%9C%AC&x%3Dy=1"
+        );
+    }
+
+    #[test]
+    fn accepts_snake_and_camel_case_keys() {
+        let snake: ReceivedCheckin = from_value(success_response()["checkin"].clone()).unwrap();
+        let camel = json!({
+            "id": 1,
+            "checkedInAt": "2021-01-01T00:00:00+09:00",
+            "note": "",
+            "link": "",
+            "tags": [],
+            "source": "webhook",
+            "isPrivate": false,
+            "isTooSensitive": false,
+        });
+        assert_eq!(from_value::<ReceivedCheckin>(camel.clone()).unwrap(), snake);
+
+        let camel = camel.to_string();
+        let borrowed: ReceivedCheckinRef<'_> = serde_json::from_str(&camel).unwrap();
+        assert_eq!(borrowed.into_owned(), snake);
+    }
+
+    #[test]
+    fn extra_headers_are_forwarded() {
+        let requester = MockRequester::with_responses(vec![success_response()]);
+        let endpoint = IncomingEndpoint::new("id", requester.clone())
+            .with_header("X-Tissue-Client", "base")
+            .with_header("X-Kept", "kept");
+
+        let mut extra = HashMap::new();
+        extra.insert("X-Tissue-Client".into(), "override".into());
+        extra.insert("X-Request".into(), "only-this".into());
+        block_on(endpoint.send_checkin_with_headers(&Checkin::builder().build(), extra)).unwrap();
+
+        let headers = requester.requests()[0].headers().clone();
+        assert_eq!(headers["X-Tissue-Client"], "override");
+        assert_eq!(headers["X-Request"], "only-this");
+        assert_eq!(headers["X-Kept"], "kept");
+
+        // Extra headers are not kept for later requests
+        requester.push_response(success_response());
+        block_on(endpoint.send_checkin(&Checkin::builder().build())).unwrap();
+        let headers = requester.requests()[1].headers().clone();
+        assert_eq!(headers["X-Tissue-Client"], "base");
+        assert!(!headers.contains_key("X-Request"));
+    }
+
+    #[test]
+    fn maps_source_kinds() {
+        let known = [
+            ("web", SourceKind::Web),
+            ("csv", SourceKind::Csv),
+            ("webhook", SourceKind::Webhook),
+            ("api", SourceKind::Api),
+        ];
+        for (source, kind) in known.iter() {
+            assert_eq!(SourceKind::from(*source), *kind);
+            assert_eq!(kind.as_str(), *source);
+        }
+
+        let unknown = SourceKind::from("twitter");
+        assert_eq!(unknown, SourceKind::Other("twitter".into()));
+        assert_eq!(unknown.as_str(), "twitter");
+        // Matching is case-sensitive as Tissue stores them in lowercase
+        assert_eq!(SourceKind::from("API"), SourceKind::Other("API".into()));
+
+        let mut checkin = success_response()["checkin"].clone();
+        checkin["source"] = "csv".into();
+        let received: ReceivedCheckin = from_value(checkin).unwrap();
+        assert_eq!(received.source_kind(), SourceKind::Csv);
+    }
+
+    #[test]
+    fn missing_status_is_malformed() {
+        let body = json!({ "checkin": success_response()["checkin"].clone() });
+        match parse_response(body.clone()) {
+            Err(TissueError::MalformedResponse(returned)) => assert_eq!(returned, body),
+            result => panic!("Unexpected result: {:?}", result),
+        }
+
+        let requester = MockRequester::with_responses(vec![json!({})]);
+        let endpoint = IncomingEndpoint::new("id", requester);
+        assert!(matches!(
+            block_on(endpoint.send_checkin(&Checkin::builder().build())),
+            Err(TissueError::MalformedResponse(_))
+        ));
+    }
+
+    #[test]
+    fn rate_limited_batch_pauses_without_retries() {
+        use std::time::Instant;
+
+        let requester = MockRequester::with_responses(vec![
+            success_response(),
+            json!({ "status": 429, "retry_after": 1 }),
+            success_response(),
+        ]);
+        let endpoint = IncomingEndpoint::new("id", requester.clone());
+        let checkins: Vec<_> = (0..3).map(|_| Checkin::builder().build()).collect();
+
+        let started = Instant::now();
+        let results = block_on(endpoint.send_checkins(&checkins, 1));
+        assert!(started.elapsed() >= Duration::from_secs(1));
+        assert!(matches!(results[0], Ok(CheckinResponse::Success(_))));
+        assert!(matches!(
+            results[1],
+            Ok(CheckinResponse::RateLimited {
+                retry_after: Some(retry_after)
+            }) if retry_after == Duration::from_secs(1)
+        ));
+        assert!(matches!(results[2], Ok(CheckinResponse::Success(_))));
+        assert_eq!(requester.requests().len(), 3);
+    }
+
+    #[test]
+    fn hidden_id_keeps_error_chain() {
+        let requester = MockRequester::new();
+        requester.push_error("failed for https://shikorism.net/api/webhooks/checkin/a: a timeout");
+        let endpoint = IncomingEndpoint::new("a", requester);
+
+        let error = block_on(endpoint.send_checkin(&Checkin::builder().build())).unwrap_err();
+        let source = error.source().unwrap();
+        assert_eq!(
+            source.to_string(),
+            "failed for https://shikorism.net/api/webhooks/checkin/***: a timeout"
+        );
+        assert!(source.source().is_some());
+    }
+
+    #[test]
+    fn base_path_is_validated() {
+        let endpoint = IncomingEndpoint::new("id", MockRequester::new())
+            .try_with_base_path("/tissue/v2/")
+            .unwrap();
+        assert_eq!(
+            endpoint.target_url(),
+            "https://shikorism.net/tissue/v2/api/webhooks/checkin/id"
+        );
+
+        for base_path in &["my tissue", "tissue?x=1", "tissue#top", "a\\b", "日本"] {
+            assert!(matches!(
+                IncomingEndpoint::new("id", MockRequester::new()).try_with_base_path(base_path),
+                Err(TissueError::InvalidUrl(_))
+            ));
         }
     }
 }